@@ -0,0 +1,24 @@
+fn main() {
+    cfg_aliases::cfg_aliases! {
+        // Platforms where winit can run the event loop off the main thread.
+        any_thread_event_loop: { any(
+            target_os = "windows",
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ) },
+        // Platforms where winit can pump the event loop.
+        pump_events: { any(
+            target_os = "windows",
+            target_os = "macos",
+            target_os = "android",
+            target_os = "linux",
+            target_os = "dragonfly",
+            target_os = "freebsd",
+            target_os = "netbsd",
+            target_os = "openbsd"
+        ) },
+    }
+}
@@ -92,9 +92,13 @@ impl wginit::ApplicationHandler for Application {
         self.gfx_state = None;
     }
 
-    fn redraw(&mut self, window: &winit::window::Window, wgpu: &wginit::Wgpu) {
+    fn redraw(
+        &mut self,
+        window: &winit::window::Window,
+        wgpu: &wginit::Wgpu,
+    ) -> Result<(), wgpu::SurfaceError> {
         let gfx_state = self.gfx_state.as_ref().unwrap();
-        let frame = wgpu.surface.get_current_texture().unwrap();
+        let frame = wgpu.surface.get_current_texture()?;
         let view = frame
             .texture
             .create_view(&wgpu::TextureViewDescriptor::default());
@@ -125,6 +129,7 @@ impl wginit::ApplicationHandler for Application {
         window.pre_present_notify();
         frame.present();
         window.request_redraw();
+        Ok(())
     }
 }
 
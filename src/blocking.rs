@@ -0,0 +1,151 @@
+//! Initializing wgpu without an [`ApplicationHandler`], on platforms where winit can pump the event loop.
+
+use crate::{ApplicationHandler, SurfaceOverrides, UserEventSender, Wgpu, WgpuInitError};
+
+/// An error that occurred during [`init_blocking`].
+#[derive(Debug)]
+pub enum InitBlockingError {
+    /// The event loop could not be created.
+    EventLoop(winit::error::EventLoopError),
+    /// The event loop exited with the given exit code before initialization finished.
+    Exited(i32),
+    /// The window could not be created.
+    CreateWindow(winit::error::OsError),
+    /// wgpu could not be initialized.
+    Wgpu(WgpuInitError),
+}
+
+impl std::fmt::Display for InitBlockingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::EventLoop(e) => write!(f, "event loop error: {e}"),
+            Self::Exited(code) => write!(f, "event loop exited with code {code}"),
+            Self::CreateWindow(e) => write!(f, "failed to create window: {e}"),
+            Self::Wgpu(e) => write!(f, "failed to initialize wgpu: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for InitBlockingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::EventLoop(e) => Some(e),
+            Self::Exited(_) => None,
+            Self::CreateWindow(e) => Some(e),
+            Self::Wgpu(e) => Some(e),
+        }
+    }
+}
+
+/// The application used to pick wgpu defaults for [`init_blocking`].
+struct BlockingApp;
+
+impl ApplicationHandler for BlockingApp {
+    type UserEvent = std::convert::Infallible;
+
+    fn new(user_event_sender: UserEventSender<Self::UserEvent>) -> Self {
+        let _ = user_event_sender;
+        Self
+    }
+}
+
+struct BlockingInitHandler {
+    window_attrs: Option<winit::window::WindowAttributes>,
+    window: Option<std::sync::Arc<winit::window::Window>>,
+    result: Option<Result<(std::sync::Arc<winit::window::Window>, Wgpu), InitBlockingError>>,
+}
+
+impl BlockingInitHandler {
+    /// Initializes wgpu once the window has a non-zero size.
+    fn try_init_wgpu(&mut self) {
+        let Some(window) = self.window.clone() else {
+            return;
+        };
+        let size = window.inner_size();
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.window = None;
+        self.result = Some(
+            pollster::block_on(Wgpu::new::<BlockingApp>(
+                window.clone(),
+                0,
+                SurfaceOverrides::default(),
+                None,
+            ))
+            .map(|wgpu| (window, wgpu))
+            .map_err(InitBlockingError::Wgpu),
+        );
+    }
+}
+
+impl winit::application::ApplicationHandler for BlockingInitHandler {
+    fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        let Some(window_attrs) = self.window_attrs.take() else {
+            return;
+        };
+        match event_loop.create_window(window_attrs) {
+            Ok(window) => {
+                self.window = Some(std::sync::Arc::new(window));
+                self.try_init_wgpu();
+            }
+            Err(e) => {
+                self.result = Some(Err(InitBlockingError::CreateWindow(e)));
+            }
+        }
+    }
+
+    fn window_event(
+        &mut self,
+        event_loop: &winit::event_loop::ActiveEventLoop,
+        window_id: winit::window::WindowId,
+        event: winit::event::WindowEvent,
+    ) {
+        let _ = (event_loop, window_id);
+        if let winit::event::WindowEvent::Resized(_) = event {
+            self.try_init_wgpu();
+        }
+    }
+}
+
+/// Creates an event loop, a window and [`Wgpu`] without going through an [`ApplicationHandler`].
+///
+/// This is intended for small tools and scripts that just want a device and a window. The event loop is pumped just long enough for wgpu to initialize, after which the initialized state is returned along with the event loop, which the caller is then responsible for driving.
+///
+/// <section class="warning">
+///
+/// As none of the [`ApplicationHandler`] machinery is involved, wginit will not reconfigure the surface on resize, handle suspension or deliver wgpu errors. The event loop has already been started, so it should be driven with [`winit::platform::pump_events::EventLoopExtPumpEvents::pump_app_events`] or [`winit::platform::run_on_demand::EventLoopExtRunOnDemand::run_app_on_demand`], and the initial [`winit::event::Event::Resumed`] will not be delivered again.
+///
+/// Only one event loop may be created per process, so this cannot be combined with [`run`](crate::run). This is not available on the web or iOS, where the event loop cannot be pumped.
+///
+/// </section>
+pub fn init_blocking(
+    window_attrs: winit::window::WindowAttributes,
+) -> Result<
+    (
+        winit::event_loop::EventLoop<()>,
+        std::sync::Arc<winit::window::Window>,
+        Wgpu,
+    ),
+    InitBlockingError,
+> {
+    use winit::platform::pump_events::{EventLoopExtPumpEvents as _, PumpStatus};
+
+    let mut event_loop =
+        winit::event_loop::EventLoop::new().map_err(InitBlockingError::EventLoop)?;
+    let mut handler = BlockingInitHandler {
+        window_attrs: Some(window_attrs),
+        window: None,
+        result: None,
+    };
+    loop {
+        let status = event_loop.pump_app_events(None, &mut handler);
+        if let Some(result) = handler.result.take() {
+            let (window, wgpu) = result?;
+            return Ok((event_loop, window, wgpu));
+        }
+        if let PumpStatus::Exit(code) = status {
+            return Err(InitBlockingError::Exited(code));
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Input state tracking, enabled by the `input` feature.
+
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+
+/// The kind of device that produced a [`PointerSample`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PointerKind {
+    /// A mouse or other cursor-based pointer.
+    Mouse,
+    /// A finger on a touch screen.
+    Touch,
+    /// A pen or stylus.
+    ///
+    /// winit does not report the touch tool directly, so this is inferred from touches that report an altitude angle, which is currently only the case for the Apple Pencil on iOS.
+    Pen,
+}
+
+/// A single pointer sample, unifying mouse, touch and pen input.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PointerSample {
+    /// The kind of device that produced this sample.
+    pub kind: PointerKind,
+    /// The touch ID, if this sample was produced by a touch or pen.
+    pub id: Option<u64>,
+    /// The position of the pointer, relative to the window.
+    pub position: winit::dpi::PhysicalPosition<f64>,
+    /// The pressure of the pointer, normalized to `0.0..=1.0`.
+    ///
+    /// This is [`None`] if the device does not report pressure, e.g. for mice.
+    pub pressure: Option<f64>,
+    /// The angle between the pen and the surface in radians, where `PI / 2` is perpendicular to it.
+    ///
+    /// This is only available for pens on some platforms.
+    pub altitude_angle: Option<f64>,
+}
+
+impl PointerSample {
+    fn from_touch(touch: &winit::event::Touch) -> Self {
+        let (pressure, altitude_angle) = match touch.force {
+            Some(force @ winit::event::Force::Calibrated { altitude_angle, .. }) => {
+                (Some(force.normalized().clamp(0.0, 1.0)), altitude_angle)
+            }
+            Some(force @ winit::event::Force::Normalized(_)) => {
+                (Some(force.normalized().clamp(0.0, 1.0)), None)
+            }
+            None => (None, None),
+        };
+        Self {
+            kind: if altitude_angle.is_some() {
+                PointerKind::Pen
+            } else {
+                PointerKind::Touch
+            },
+            id: Some(touch.id),
+            position: touch.location,
+            pressure,
+            altitude_angle,
+        }
+    }
+}
+
+/// Input state accumulated from window events before they are dispatched to the application.
+#[derive(Default)]
+pub(crate) struct InputState {
+    pub(crate) dropped_files: Vec<PathBuf>,
+    pub(crate) hovered_files: Vec<PathBuf>,
+    pub(crate) pointer_samples: Vec<PointerSample>,
+    pub(crate) held_physical_keys: HashSet<winit::keyboard::KeyCode>,
+    pub(crate) pressed_physical_keys: HashSet<winit::keyboard::KeyCode>,
+    /// The logical key each held physical key produced when it was pressed, as e.g. modifiers may change the logical key by the time it is released.
+    pub(crate) held_logical_keys: HashMap<winit::keyboard::PhysicalKey, winit::keyboard::Key>,
+}
+
+impl InputState {
+    /// Updates the input state from a window event.
+    pub(crate) fn handle_window_event(&mut self, event: &winit::event::WindowEvent) {
+        match event {
+            winit::event::WindowEvent::HoveredFile(path) => {
+                self.hovered_files.push(path.clone());
+            }
+            winit::event::WindowEvent::HoveredFileCancelled => {
+                self.hovered_files.clear();
+            }
+            winit::event::WindowEvent::DroppedFile(path) => {
+                self.hovered_files.retain(|hovered| hovered != path);
+                self.dropped_files.push(path.clone());
+            }
+            winit::event::WindowEvent::CursorMoved { position, .. } => {
+                self.pointer_samples.push(PointerSample {
+                    kind: PointerKind::Mouse,
+                    id: None,
+                    position: *position,
+                    pressure: None,
+                    altitude_angle: None,
+                });
+            }
+            winit::event::WindowEvent::Touch(touch) => {
+                self.pointer_samples.push(PointerSample::from_touch(touch));
+            }
+            winit::event::WindowEvent::KeyboardInput { event, .. } => match event.state {
+                winit::event::ElementState::Pressed => {
+                    if let winit::keyboard::PhysicalKey::Code(code) = event.physical_key {
+                        if !event.repeat {
+                            self.pressed_physical_keys.insert(code);
+                        }
+                        self.held_physical_keys.insert(code);
+                    }
+                    self.held_logical_keys
+                        .insert(event.physical_key, event.logical_key.clone());
+                }
+                winit::event::ElementState::Released => {
+                    if let winit::keyboard::PhysicalKey::Code(code) = event.physical_key {
+                        self.held_physical_keys.remove(&code);
+                    }
+                    self.held_logical_keys.remove(&event.physical_key);
+                }
+            },
+            winit::event::WindowEvent::Focused(false) => {
+                // Releases are not delivered while unfocused, so forget held keys rather than having them stuck.
+                self.held_physical_keys.clear();
+                self.held_logical_keys.clear();
+            }
+            _ => {}
+        }
+    }
+
+    /// Clears per-frame input state.
+    pub(crate) fn end_frame(&mut self) {
+        self.dropped_files.clear();
+        self.pointer_samples.clear();
+        self.pressed_physical_keys.clear();
+    }
+}
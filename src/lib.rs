@@ -2,6 +2,24 @@
 //!
 //! It only handles one device.
 
+#[cfg(pump_events)]
+mod blocking;
+#[cfg(feature = "input")]
+mod input;
+mod lifecycle;
+mod oneshot;
+#[cfg(not(target_arch = "wasm32"))]
+mod pool;
+#[cfg(any_thread_event_loop)]
+mod thread;
+
+#[cfg(pump_events)]
+pub use blocking::{init_blocking, InitBlockingError};
+#[cfg(feature = "input")]
+pub use input::{PointerKind, PointerSample};
+#[cfg(any_thread_event_loop)]
+pub use thread::{run_on_thread, AppHandle};
+
 pub use wgpu;
 pub use winit;
 
@@ -23,6 +41,8 @@ pub struct Context<'a> {
 
     /// The current wgpu state. This may be [`None`] if the wgpu state is not available yet, or was destroyed.
     pub wgpu: Option<&'a Wgpu>,
+
+    shared: &'a SharedState,
 }
 
 impl<'a> Context<'a> {
@@ -30,110 +50,1426 @@ impl<'a> Context<'a> {
         event_loop: &'a winit::event_loop::ActiveEventLoop,
         window: Option<&'a winit::window::Window>,
         wgpu: Option<&'a Wgpu>,
+        shared: &'a SharedState,
     ) -> Self {
         Self {
             event_loop,
             window,
             wgpu,
+            shared,
+        }
+    }
+
+    /// Calls the closure with [`Context::wgpu`] only if it is available.
+    pub fn with_wgpu(&self, f: impl FnOnce(&Wgpu)) {
+        if let Some(wgpu) = self.wgpu {
+            f(wgpu);
+        }
+    }
+
+    /// Calls the closure with [`Context::window`] and [`Context::wgpu`] only if both are available.
+    pub fn with_ready(&self, f: impl FnOnce(&winit::window::Window, &Wgpu)) {
+        if let (Some(window), Some(wgpu)) = (self.window, self.wgpu) {
+            f(window, wgpu);
+        }
+    }
+
+    /// Gets the shared data created by [`ApplicationHandler::create_shared_data`].
+    ///
+    /// This returns [`None`] if there is no shared data or it is not of type `T`.
+    ///
+    /// <section class="warning">
+    ///
+    /// This panics if the data is currently borrowed mutably via [`Context::data_mut`].
+    ///
+    /// </section>
+    pub fn data<T>(&self) -> Option<std::cell::Ref<'_, T>>
+    where
+        T: 'static,
+    {
+        std::cell::Ref::filter_map(self.shared.data.borrow(), |data| {
+            data.as_ref()?.downcast_ref::<T>()
+        })
+        .ok()
+    }
+
+    /// Gets the shared data created by [`ApplicationHandler::create_shared_data`] mutably.
+    ///
+    /// This returns [`None`] if there is no shared data or it is not of type `T`.
+    ///
+    /// <section class="warning">
+    ///
+    /// This panics if the data is currently borrowed via [`Context::data`] or [`Context::data_mut`].
+    ///
+    /// </section>
+    pub fn data_mut<T>(&self) -> Option<std::cell::RefMut<'_, T>>
+    where
+        T: 'static,
+    {
+        std::cell::RefMut::filter_map(self.shared.data.borrow_mut(), |data| {
+            data.as_mut()?.downcast_mut::<T>()
+        })
+        .ok()
+    }
+
+    /// Gets the files dropped onto the window since the last redraw.
+    ///
+    /// This is cleared after every [`winit::event::WindowEvent::RedrawRequested`] is dispatched.
+    #[cfg(feature = "input")]
+    pub fn dropped_files(&self) -> &[std::path::PathBuf] {
+        &self.shared.input.dropped_files
+    }
+
+    /// Gets the files currently being dragged over the window.
+    #[cfg(feature = "input")]
+    pub fn hovered_files(&self) -> &[std::path::PathBuf] {
+        &self.shared.input.hovered_files
+    }
+
+    /// Returns if the key at the given physical location is held down.
+    ///
+    /// Physical keys are independent of the keyboard layout, so this is the right choice for position-based controls such as WASD movement in games.
+    #[cfg(feature = "input")]
+    pub fn is_physical_key_held(&self, code: winit::keyboard::KeyCode) -> bool {
+        self.shared.input.held_physical_keys.contains(&code)
+    }
+
+    /// Returns if the key at the given physical location was pressed since the last redraw.
+    ///
+    /// Unlike [`Context::is_physical_key_held`], this ignores key repeats, so it only reports the initial press. This is cleared after every [`winit::event::WindowEvent::RedrawRequested`] is dispatched.
+    #[cfg(feature = "input")]
+    pub fn is_physical_key_pressed(&self, code: winit::keyboard::KeyCode) -> bool {
+        self.shared.input.pressed_physical_keys.contains(&code)
+    }
+
+    /// Returns if a key producing the given logical key is held down.
+    ///
+    /// Logical keys depend on the keyboard layout and modifiers, so this is the right choice for bindings that refer to what is printed on the key, such as `Ctrl+Z`. The logical key is remembered from when the key was pressed, so e.g. releasing Shift before the key does not leave it stuck.
+    #[cfg(feature = "input")]
+    pub fn is_logical_key_held(&self, key: winit::keyboard::Key) -> bool {
+        self.shared
+            .input
+            .held_logical_keys
+            .values()
+            .any(|held| *held == key)
+    }
+
+    /// Gets the mouse, touch and pen samples received since the last redraw, in the order they arrived.
+    ///
+    /// Drawing applications can use this as a single input stream instead of handling [`winit::event::WindowEvent::CursorMoved`] and [`winit::event::WindowEvent::Touch`] separately. This is cleared after every [`winit::event::WindowEvent::RedrawRequested`] is dispatched.
+    #[cfg(feature = "input")]
+    pub fn pointer_samples(&self) -> &[PointerSample] {
+        &self.shared.input.pointer_samples
+    }
+
+    /// Returns if the application has never been suspended, i.e. this is the initial resume.
+    ///
+    /// On platforms such as Android, the application may be resumed many times. Every resume recreates the wgpu state (including the surface, as the native window handle may change), but only the first one is a cold start.
+    ///
+    /// Reinitializing wgpu via [`Context::reinitialize_wgpu`], losing the device (see [`ApplicationHandler::adapter_lost`]) and closing the window with [`ApplicationHandler::keep_running_after_close`] set also count as a suspension.
+    pub fn is_cold_start(&self) -> bool {
+        self.shared.lifecycle.is_cold_start()
+    }
+
+    /// Changes the present mode of the surface, e.g. to toggle VSync from a settings menu.
+    ///
+    /// The surface is reconfigured with the new present mode once the current event has been handled, keeping the rest of its configuration, and a redraw is requested. The present mode is kept across resizes and suspensions. If the present mode is not supported by the surface, the present mode from [`ApplicationHandler::surface_configuration`] is used instead.
+    pub fn set_present_mode(&self, present_mode: wgpu::PresentMode) {
+        let mut surface_overrides = self.shared.surface_overrides.get();
+        surface_overrides.present_mode = Some(present_mode);
+        self.shared.surface_overrides.set(surface_overrides);
+        self.shared.reconfigure_requested.set(true);
+    }
+
+    /// Requests the window and wgpu state to be created.
+    ///
+    /// This is only needed if [`ApplicationHandler::defer_window_creation`] is set, or to reopen the window after it was closed with [`ApplicationHandler::keep_running_after_close`] set. The window is created once the current event has been handled, and [`ApplicationHandler::resumed`] will be called when both the window and wgpu are ready.
+    ///
+    /// This can be called from any handler that receives a [`Context`], such as [`ApplicationHandler::new_events`], [`ApplicationHandler::user_event`] or [`ApplicationHandler::about_to_wait`]. If it is called before the event loop has been resumed, the window is created once it is. If the window already exists, this does nothing.
+    pub fn create_window(&self) {
+        // Otherwise, the request would linger and reopen the window as soon as it is closed.
+        if self.window.is_some() {
+            return;
         }
+        self.shared.create_window_requested.set(true);
+    }
+
+    /// Requests wgpu to be torn down and initialized again, e.g. after changing GPU or feature settings.
+    ///
+    /// This happens once the current event has been handled. The adapter and device are requested again via hooks such as [`ApplicationHandler::device_descriptor`], so any settings they read will take effect. As with a real suspension, all GPU resources must be recreated in [`ApplicationHandler::resumed`], which will be called again when wgpu is ready.
+    ///
+    /// This does nothing if wgpu is not currently initialized.
+    pub fn reinitialize_wgpu(&self) {
+        self.shared.reinitialize_requested.set(true);
+    }
+
+    /// Gets the current placement of the window, e.g. to persist it across launches.
+    ///
+    /// This returns [`None`] if the window is not available.
+    pub fn window_placement(&self) -> Option<WindowPlacement> {
+        let window = self.window?;
+        Some(WindowPlacement {
+            position: window.outer_position().ok(),
+            inner_size: window.inner_size(),
+        })
+    }
+
+    /// Restores a placement previously retrieved with [`Context::window_placement`].
+    ///
+    /// The position is only restored on platforms that support it. This does nothing if the window is not available.
+    pub fn set_window_placement(&self, placement: WindowPlacement) {
+        let Some(window) = self.window else {
+            return;
+        };
+        if let Some(position) = placement.position {
+            window.set_outer_position(position);
+        }
+        let _ = window.request_inner_size(placement.inner_size);
+    }
+
+    /// Gets the raw window and display handles of the window, e.g. for interop with libraries that create their own surfaces or overlays.
+    ///
+    /// This returns [`None`] if the window is not available, or if the handles are currently unavailable (e.g. on Android while suspended).
+    pub fn raw_handles(
+        &self,
+    ) -> Option<(
+        winit::raw_window_handle::RawWindowHandle,
+        winit::raw_window_handle::RawDisplayHandle,
+    )> {
+        use winit::raw_window_handle::{HasDisplayHandle as _, HasWindowHandle as _};
+
+        let window = self.window?;
+        Some((
+            window.window_handle().ok()?.as_raw(),
+            window.display_handle().ok()?.as_raw(),
+        ))
+    }
+
+    /// Gets the scale factor of the window, e.g. for DPI-aware layout.
+    ///
+    /// Changes are notified via [`ApplicationHandler::scale_factor_changed`]. This returns [`None`] if the window is not available.
+    pub fn scale_factor(&self) -> Option<f64> {
+        Some(self.window?.scale_factor())
+    }
+
+    /// Gets all monitors that are currently available, e.g. to populate a fullscreen monitor setting.
+    pub fn available_monitors(&self) -> Vec<winit::monitor::MonitorHandle> {
+        self.event_loop.available_monitors().collect()
     }
+
+    /// Makes the window fullscreen on a specific monitor.
+    ///
+    /// If a video mode is given, exclusive fullscreen is used with that mode, which must be one of the [`winit::monitor::MonitorHandle::video_modes`] of the monitor. Otherwise, borderless fullscreen is used. The surface is reconfigured to the new size once the window is resized.
+    ///
+    /// This does nothing if the window is not available.
+    pub fn set_fullscreen_on(
+        &self,
+        monitor: winit::monitor::MonitorHandle,
+        mode: Option<winit::monitor::VideoModeHandle>,
+    ) {
+        let Some(window) = self.window else {
+            return;
+        };
+        window.set_fullscreen(Some(match mode {
+            Some(mode) => winit::window::Fullscreen::Exclusive(mode),
+            None => winit::window::Fullscreen::Borderless(Some(monitor)),
+        }));
+    }
+
+    /// Sets the cursor icon of the window.
+    ///
+    /// This does nothing if the window is not available.
+    pub fn set_cursor_icon(&self, icon: winit::window::CursorIcon) {
+        if let Some(window) = self.window {
+            window.set_cursor(icon);
+        }
+    }
+
+    /// Sets a custom image cursor for the window.
+    ///
+    /// Custom cursors can be created once with [`winit::event_loop::ActiveEventLoop::create_custom_cursor`] and reused.
+    ///
+    /// This does nothing if the window is not available.
+    pub fn set_custom_cursor(&self, cursor: &winit::window::CustomCursor) {
+        if let Some(window) = self.window {
+            window.set_cursor(cursor.clone());
+        }
+    }
+
+    /// Requests a redraw of the window.
+    ///
+    /// Redraw requests are coalesced: however many times a redraw is requested, by wginit or the application, only one [`winit::event::WindowEvent::RedrawRequested`] is delivered, after which new requests can be made again: requesting a redraw while handling [`winit::event::WindowEvent::RedrawRequested`] in [`ApplicationHandler::window_event`] schedules another one. As [`ApplicationHandler::redraw`] does not receive a [`Context`], use a [`ContinuousToken`] to keep redrawing instead of calling [`winit::window::Window::request_redraw`] from there, which bypasses the coalescing. This does nothing if the window is not available.
+    pub fn request_redraw(&self) {
+        if let Some(window) = self.window {
+            self.shared.request_redraw(window);
+        }
+    }
+
+    /// Returns if a redraw has been requested via [`Context::request_redraw`] (or internally by wginit) and not been handled yet.
+    ///
+    /// Redraws requested directly via [`winit::window::Window::request_redraw`] are not tracked.
+    pub fn is_redraw_pending(&self) -> bool {
+        self.shared.redraw_pending.get()
+    }
+
+    /// Requests continuous rendering for as long as the returned [`ContinuousToken`] is alive.
+    ///
+    /// While any token is alive, the next redraw is requested as soon as the previous [`ApplicationHandler::redraw`] has returned, so the event loop keeps running without the application having to request redraws itself. Once all tokens are dropped, redraws are no longer requested. This is useful for e.g. audio or video streams that only need continuous updates while they are playing.
+    ///
+    /// Unlike [`winit::event_loop::ControlFlow::Poll`], this does not busy-loop: redraws are paced by the surface's present mode (e.g. [`wgpu::PresentMode::Fifo`] limits them to the display's refresh rate) and by the compositor, such as via frame callbacks on Wayland when [`winit::window::Window::pre_present_notify`] is called before presenting. [`ApplicationHandler::next_control_flow`] is unaffected.
+    pub fn request_continuous(&self) -> ContinuousToken {
+        self.shared
+            .continuous_tokens
+            .fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        ContinuousToken {
+            count: self.shared.continuous_tokens.clone(),
+        }
+    }
+
+    /// Sets whether the window should have decorations, such as a title bar and borders, e.g. for applications that draw their own window chrome.
+    ///
+    /// As the client area may change, a redraw is requested afterwards. This does nothing if the window is not available, or on platforms where decorations are not supported.
+    pub fn set_decorations(&self, decorated: bool) {
+        if let Some(window) = self.window {
+            window.set_decorations(decorated);
+            self.shared.request_redraw(window);
+        }
+    }
+
+    /// Minimizes or restores the window, e.g. from custom window controls.
+    ///
+    /// This does nothing if the window is not available, or on platforms where minimizing is not supported (such as iOS, Android, the web and Wayland when restoring).
+    pub fn set_minimized(&self, minimized: bool) {
+        if let Some(window) = self.window {
+            window.set_minimized(minimized);
+        }
+    }
+
+    /// Maximizes or restores the window, e.g. from custom window controls.
+    ///
+    /// This does nothing if the window is not available, or on platforms where maximizing is not supported (such as iOS, Android and the web).
+    pub fn set_maximized(&self, maximized: bool) {
+        if let Some(window) = self.window {
+            window.set_maximized(maximized);
+        }
+    }
+
+    /// Starts moving the window with the mouse, e.g. from a custom title bar.
+    ///
+    /// This should be called while the left mouse button is pressed. It is supported on Windows, macOS, X11 and Wayland, and does nothing elsewhere or if the window is not available.
+    pub fn drag_window(&self) {
+        if let Some(window) = self.window {
+            let _ = window.drag_window();
+        }
+    }
+
+    /// Sets whether the window should stay on top of other windows.
+    ///
+    /// This does nothing if the window is not available, or on platforms where window levels are not supported.
+    pub fn set_always_on_top(&self, on_top: bool) {
+        if let Some(window) = self.window {
+            window.set_window_level(if on_top {
+                winit::window::WindowLevel::AlwaysOnTop
+            } else {
+                winit::window::WindowLevel::Normal
+            });
+        }
+    }
+}
+
+/// A token that keeps continuous rendering active while it is alive.
+///
+/// See [`Context::request_continuous`]. The token can be sent to other threads, e.g. to the thread running an audio stream.
+#[derive(Debug)]
+pub struct ContinuousToken {
+    count: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl Drop for ContinuousToken {
+    fn drop(&mut self) {
+        self.count
+            .fetch_sub(1, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+/// A trackpad or touch gesture.
+///
+/// See [`ApplicationHandler::gesture`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum GestureEvent {
+    /// A two-finger pinch, e.g. for zooming.
+    Pinch {
+        /// The change in scale, where positive values zoom in.
+        delta: f64,
+        /// The phase of the gesture.
+        phase: winit::event::TouchPhase,
+    },
+    /// A two-finger rotation.
+    Rotation {
+        /// The change in rotation in radians, where positive values are counterclockwise.
+        delta: f32,
+        /// The phase of the gesture.
+        phase: winit::event::TouchPhase,
+    },
+    /// A multi-finger pan.
+    Pan {
+        /// The change in position.
+        delta: winit::dpi::PhysicalPosition<f32>,
+        /// The phase of the gesture.
+        phase: winit::event::TouchPhase,
+    },
+}
+
+/// The placement of a window.
+///
+/// With the `serde` feature, this can be serialized to persist window geometry across launches.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct WindowPlacement {
+    /// The outer position of the window.
+    ///
+    /// This is [`None`] on platforms where the window position is not available, such as Wayland and the web.
+    pub position: Option<winit::dpi::PhysicalPosition<i32>>,
+    /// The inner size of the window.
+    pub inner_size: winit::dpi::PhysicalSize<u32>,
 }
 
 /// The current wgpu state.
+///
+/// When dropped, the surface and pipeline cache are dropped before the device and queue, as dropping the device first causes warnings or hangs on some backends.
 pub struct Wgpu {
+    // Fields are dropped in declaration order, so the surface and pipeline cache must come before the device and queue.
+    /// The current [`wgpu::Surface`].
+    pub surface: wgpu::Surface<'static>,
+    /// The [`wgpu::PipelineCache`], if supported by the device.
+    ///
+    /// Pass this into pipeline descriptors to cut shader compilation times across launches. It is only available if the device was created with [`wgpu::Features::PIPELINE_CACHE`].
+    pub pipeline_cache: Option<wgpu::PipelineCache>,
     /// The current [`wgpu::Device`].
     pub device: wgpu::Device,
     /// The current [`wgpu::Queue`].
     pub queue: wgpu::Queue,
+    /// The [`wgpu::Instance`] the adapter was requested from.
+    pub instance: wgpu::Instance,
     /// The current [`wgpu::Adapter`].
     pub adapter: wgpu::Adapter,
-    /// The current [`wgpu::Surface`].
-    pub surface: wgpu::Surface<'static>,
+    /// The [`wgpu::DownlevelCapabilities`] of the current adapter.
+    pub downlevel_capabilities: wgpu::DownlevelCapabilities,
+    /// The current [`wgpu::SurfaceConfiguration`] the surface is configured with.
+    ///
+    /// This includes e.g. the chosen [`wgpu::CompositeAlphaMode`].
+    pub surface_config: wgpu::SurfaceConfiguration,
     /// The current counter for times the wgpu state has been suspended.
     ///
     /// This can be useful to determine if the wgpu state was reinitialized from the last time the wgpu state was passed.
     pub suspend_count: u64,
+    /// How long initializing this wgpu state took, e.g. for startup profiling.
+    pub init_timings: InitTimings,
+
+    requested_present_mode: wgpu::PresentMode,
+    surface_configured: bool,
+    lost: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    label_prefix: &'static str,
+}
+
+/// A breakdown of how long initializing wgpu took.
+///
+/// See [`Wgpu::init_timings`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InitTimings {
+    /// The total time, from creating the [`wgpu::Instance`] until the surface was configured.
+    pub total: std::time::Duration,
+    /// The time spent requesting the [`wgpu::Adapter`], including the fallback attempt of [`ApplicationHandler::allow_fallback_adapter`].
+    ///
+    /// This excludes probing each group of [`ApplicationHandler::backend_order`] for an adapter while creating the [`wgpu::Instance`], which only counts towards [`InitTimings::total`].
+    pub adapter: std::time::Duration,
+    /// The time spent requesting the [`wgpu::Device`].
+    pub device: std::time::Duration,
+    /// The time spent configuring the [`wgpu::Surface`].
+    pub surface: std::time::Duration,
+}
+
+/// An error that occurred while initializing wgpu.
+#[derive(Debug)]
+pub enum WgpuInitError {
+    /// The [`wgpu::Surface`] could not be created.
+    CreateSurface(wgpu::CreateSurfaceError),
+    /// No [`wgpu::Adapter`] could be found, even after falling back on GL and a fallback adapter.
+    AdapterNotFound,
+    /// The [`wgpu::Device`] could not be created.
+    RequestDevice(wgpu::RequestDeviceError),
+    /// WebGPU is not available and [`ApplicationHandler::require_webgpu`] prevented falling back on WebGL.
+    ///
+    /// This is only returned on the web.
+    WebGpuUnavailable,
+    /// The surface does not support the requested [`wgpu::TextureUsages`] with the given format, either because of the surface capabilities or the format's features.
+    ///
+    /// See [`ApplicationHandler::surface_usage`].
+    UnsupportedSurfaceUsage {
+        /// The surface or view format.
+        format: wgpu::TextureFormat,
+        /// The requested usages that are not supported.
+        usage: wgpu::TextureUsages,
+    },
+}
+
+impl std::fmt::Display for WgpuInitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateSurface(e) => write!(f, "failed to create surface: {e}"),
+            Self::AdapterNotFound => write!(f, "failed to find an appropriate adapter"),
+            Self::RequestDevice(e) => write!(f, "failed to create device: {e}"),
+            Self::WebGpuUnavailable => write!(f, "WebGPU is not available"),
+            Self::UnsupportedSurfaceUsage { format, usage } => {
+                write!(f, "surface does not support {usage:?} with {format:?}")
+            }
+        }
+    }
 }
 
+impl std::error::Error for WgpuInitError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CreateSurface(e) => Some(e),
+            Self::AdapterNotFound => None,
+            Self::RequestDevice(e) => Some(e),
+            Self::WebGpuUnavailable => None,
+            Self::UnsupportedSurfaceUsage { .. } => None,
+        }
+    }
+}
+
+/// An error that occurred while creating an additional surface via [`Wgpu::create_additional_surface`].
+#[derive(Debug)]
+pub enum AdditionalSurfaceError {
+    /// The [`wgpu::Surface`] could not be created.
+    CreateSurface(wgpu::CreateSurfaceError),
+    /// The surface is not compatible with the current [`wgpu::Adapter`], so it cannot be configured.
+    Incompatible,
+}
+
+impl std::fmt::Display for AdditionalSurfaceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::CreateSurface(e) => write!(f, "failed to create surface: {e}"),
+            Self::Incompatible => write!(f, "surface is not compatible with the adapter"),
+        }
+    }
+}
+
+impl std::error::Error for AdditionalSurfaceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::CreateSurface(e) => Some(e),
+            Self::Incompatible => None,
+        }
+    }
+}
+
+/// A report of the requirements an adapter does not meet.
+///
+/// See [`Wgpu::check_requirements`].
+#[derive(Debug, Clone)]
+pub struct RequirementReport {
+    /// The required features the adapter does not support.
+    pub missing_features: wgpu::Features,
+    /// The required limits the adapter falls short of.
+    pub insufficient_limits: Vec<LimitShortfall>,
+}
+
+/// A limit an adapter falls short of.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitShortfall {
+    /// The name of the limit, as in [`wgpu::Limits`].
+    pub name: &'static str,
+    /// The required value.
+    pub required: u64,
+    /// The value the adapter supports.
+    pub available: u64,
+}
+
+impl std::fmt::Display for RequirementReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "adapter does not meet requirements")?;
+        if !self.missing_features.is_empty() {
+            write!(f, "; missing features: {:?}", self.missing_features)?;
+        }
+        for shortfall in &self.insufficient_limits {
+            write!(
+                f,
+                "; {} is {} but {} is required",
+                shortfall.name, shortfall.available, shortfall.required
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RequirementReport {}
+
 impl Wgpu {
-    async fn new<A>(window: std::sync::Arc<winit::window::Window>, suspend_count: u64) -> Self
+    /// Initializes wgpu for a window.
+    ///
+    /// If an error queue is given, uncaptured errors are pushed to it from the moment the device is created, so that errors during initialization are caught as well. Otherwise, wgpu's default handler is kept.
+    #[cfg(any(pump_events, target_arch = "wasm32"))]
+    async fn new<A>(
+        window: std::sync::Arc<winit::window::Window>,
+        suspend_count: u64,
+        overrides: SurfaceOverrides,
+        errors: Option<WgpuErrors>,
+    ) -> Result<Self, WgpuInitError>
+    where
+        A: ApplicationHandler,
+    {
+        let start = web_time::Instant::now();
+        let instance = new_wgpu_instance::<A>().await?;
+        let surface = instance
+            .create_surface(window.clone())
+            .map_err(WgpuInitError::CreateSurface)?;
+        Self::from_surface::<A>(PendingWgpu {
+            start,
+            instance,
+            surface,
+            window,
+            suspend_count,
+            overrides,
+            errors,
+        })
+        .await
+    }
+
+    /// Finishes initializing wgpu for a window whose instance and surface have already been created.
+    ///
+    /// This only requests the adapter and device and configures the surface, so unlike creating the instance and surface, it may run on any thread.
+    async fn from_surface<A>(pending: PendingWgpu) -> Result<Self, WgpuInitError>
     where
         A: ApplicationHandler,
     {
-        let instance = new_wgpu_instance().await;
+        let PendingWgpu {
+            start,
+            instance,
+            surface,
+            window,
+            suspend_count,
+            overrides,
+            errors,
+        } = pending;
+
+        let adapter_start = web_time::Instant::now();
+        let mut request_adapter_options = A::request_adapter_options(&surface);
+        let mut adapter = instance.request_adapter(&request_adapter_options).await;
+        if adapter.is_none()
+            && A::allow_fallback_adapter()
+            && !request_adapter_options.force_fallback_adapter
+        {
+            // As a last resort (e.g. on headless CI without a GPU), try a software adapter.
+            request_adapter_options.force_fallback_adapter = true;
+            adapter = instance.request_adapter(&request_adapter_options).await;
+        }
+        let adapter = adapter.ok_or(WgpuInitError::AdapterNotFound)?;
+        let adapter_duration = adapter_start.elapsed();
+
+        let device_start = web_time::Instant::now();
+        let (device, queue) = adapter
+            .request_device(&A::device_descriptor(&adapter), None)
+            .await
+            .map_err(WgpuInitError::RequestDevice)?;
+        let device_duration = device_start.elapsed();
+
+        if let Some(errors) = errors {
+            device.on_uncaptured_error(Box::new(move |error| {
+                errors.lock().unwrap().push((error, None));
+            }));
+        }
+
+        let lost = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        device.set_device_lost_callback({
+            let lost = lost.clone();
+            move |reason, _| {
+                // The application destroying the device on purpose via wgpu::Device::destroy is not a loss. wginit itself tears down by dropping the device, after which this flag is no longer looked at.
+                if !matches!(reason, wgpu::DeviceLostReason::Destroyed) {
+                    lost.store(true, std::sync::atomic::Ordering::Release);
+                }
+            }
+        });
+
+        let pipeline_cache = device
+            .features()
+            .contains(wgpu::Features::PIPELINE_CACHE)
+            .then(|| {
+                let data = A::pipeline_cache_data();
+                // SAFETY: ApplicationHandler::pipeline_cache_data is documented to only return data previously returned by Wgpu::pipeline_cache_data.
+                unsafe {
+                    device.create_pipeline_cache(&wgpu::PipelineCacheDescriptor {
+                        label: Some(&format!("{} pipeline cache", A::object_label_prefix())),
+                        data: data.as_deref(),
+                        fallback: true,
+                    })
+                }
+            });
+
+        let surface_start = web_time::Instant::now();
+        let (surface_config, requested_present_mode) =
+            surface_configuration::<A>(&surface, &adapter, window.inner_size(), overrides);
+        validate_surface_usage(&surface, &adapter, &surface_config)?;
+        surface.configure(&device, &surface_config);
+        let surface_duration = surface_start.elapsed();
+
+        Ok(Self {
+            device,
+            queue,
+            instance,
+            downlevel_capabilities: adapter.get_downlevel_capabilities(),
+            adapter,
+            surface,
+            pipeline_cache,
+            surface_config,
+            suspend_count,
+            init_timings: InitTimings {
+                total: start.elapsed(),
+                adapter: adapter_duration,
+                device: device_duration,
+                surface: surface_duration,
+            },
+            requested_present_mode,
+            surface_configured: true,
+            lost,
+            label_prefix: A::object_label_prefix(),
+        })
+    }
+
+    /// Reconfigures the surface for a new size.
+    ///
+    /// This does nothing if [`ApplicationHandler::manage_surface`] is disabled.
+    fn configure_surface<A>(
+        &mut self,
+        size: winit::dpi::PhysicalSize<u32>,
+        overrides: SurfaceOverrides,
+    ) where
+        A: ApplicationHandler,
+    {
+        if !A::manage_surface() {
+            return;
+        }
+        (self.surface_config, self.requested_present_mode) =
+            surface_configuration::<A>(&self.surface, &self.adapter, size, overrides);
+        self.surface.configure(&self.device, &self.surface_config);
+        self.surface_configured = true;
+    }
+
+    /// Returns if the surface is currently configured, i.e. whether it is safe to call [`wgpu::Surface::get_current_texture`].
+    ///
+    /// The surface is configured when wgpu is initialized, and becomes unconfigured when [`ApplicationHandler::redraw`] reports it as lost or outdated, until wginit reconfigures it. wginit does not call [`ApplicationHandler::redraw`] while the surface is unconfigured. If [`ApplicationHandler::manage_surface`] is disabled, this only reflects the initial configuration.
+    pub fn is_surface_configured(&self) -> bool {
+        self.surface_configured
+    }
 
-        let surface = instance.create_surface(window.clone()).unwrap();
+    /// Gets the present mode the surface is configured with, e.g. to display whether VSync is on.
+    ///
+    /// [`wgpu::PresentMode::AutoVsync`] and [`wgpu::PresentMode::AutoNoVsync`] are resolved to the mode wgpu will pick for them. Note that drivers and compositors may still override the effective behavior.
+    pub fn present_mode(&self) -> wgpu::PresentMode {
+        let present_modes = self.surface.get_capabilities(&self.adapter).present_modes;
+        let candidates: &[wgpu::PresentMode] = match self.surface_config.present_mode {
+            wgpu::PresentMode::AutoVsync => {
+                &[wgpu::PresentMode::FifoRelaxed, wgpu::PresentMode::Fifo]
+            }
+            wgpu::PresentMode::AutoNoVsync => &[
+                wgpu::PresentMode::Immediate,
+                wgpu::PresentMode::Mailbox,
+                wgpu::PresentMode::Fifo,
+            ],
+            present_mode => return present_mode,
+        };
+        candidates
+            .iter()
+            .copied()
+            .find(|present_mode| present_modes.contains(present_mode))
+            .unwrap_or(wgpu::PresentMode::Fifo)
+    }
+
+    /// Gets the present mode that was requested, either via [`Context::set_present_mode`] or the surface configuration hooks.
+    ///
+    /// Unless an automatic mode was requested, if this differs from [`Wgpu::present_mode`], the requested mode is not supported and wginit fell back to another one.
+    pub fn requested_present_mode(&self) -> wgpu::PresentMode {
+        self.requested_present_mode
+    }
+
+    /// Gets the current contents of [`Wgpu::pipeline_cache`], e.g. to persist them on exit.
+    ///
+    /// The data can be passed back via [`ApplicationHandler::pipeline_cache_data`] on the next launch.
+    pub fn pipeline_cache_data(&self) -> Option<Vec<u8>> {
+        self.pipeline_cache.as_ref()?.get_data()
+    }
+
+    /// Checks whether the adapter meets the given requirements, e.g. to show a clear message about what the GPU is missing.
+    ///
+    /// This is a diagnostic that does not affect the device, which has already been created with the requirements from [`ApplicationHandler::device_descriptor`].
+    pub fn check_requirements(
+        &self,
+        required_features: wgpu::Features,
+        required_limits: &wgpu::Limits,
+    ) -> Result<(), RequirementReport> {
+        let missing_features = required_features - self.adapter.features();
+        let mut insufficient_limits = vec![];
+        required_limits.check_limits_with_fail_fn(
+            &self.adapter.limits(),
+            false,
+            |name, required, available| {
+                insufficient_limits.push(LimitShortfall {
+                    name,
+                    required,
+                    available,
+                });
+            },
+        );
+        if missing_features.is_empty() && insufficient_limits.is_empty() {
+            return Ok(());
+        }
+        Err(RequirementReport {
+            missing_features,
+            insufficient_limits,
+        })
+    }
+
+    /// Returns if the adapter is not fully WebGPU compliant, e.g. when running on WebGL.
+    ///
+    /// Downlevel adapters may lack features such as compute shaders; see [`Wgpu::downlevel_capabilities`] for details.
+    pub fn is_downlevel(&self) -> bool {
+        !self.downlevel_capabilities.is_webgpu_compliant()
+            || self.adapter.get_info().backend == wgpu::Backend::Gl
+    }
+
+    /// Creates a [`wgpu::CommandEncoder`] with the given label.
+    ///
+    /// Labels show up in GPU debuggers such as RenderDoc or PIX. Use [`wgpu::CommandEncoder::push_debug_group`] and [`wgpu::CommandEncoder::pop_debug_group`] to further group commands.
+    pub fn create_labeled_encoder(&self, label: &str) -> wgpu::CommandEncoder {
+        self.device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: Some(label) })
+    }
+
+    /// Creates a view of a surface texture, using the first of [`ApplicationHandler::surface_view_formats`] if any.
+    pub fn surface_view(&self, texture: &wgpu::Texture) -> wgpu::TextureView {
+        texture.create_view(&wgpu::TextureViewDescriptor {
+            format: self.surface_config.view_formats.first().copied(),
+            ..Default::default()
+        })
+    }
+
+    /// Registers a callback that is called once all work submitted to the queue so far has completed, e.g. to free staging buffers after an upload.
+    ///
+    /// On native platforms, callbacks only fire when the device is polled. wginit polls the device without blocking before every [`ApplicationHandler::about_to_wait`], so the callback fires on the first event loop iteration after the work has completed. Note that with [`winit::event_loop::ControlFlow::Wait`], this may not happen until the next event arrives. On the web, the callback fires on its own.
+    pub fn on_submitted_work_done(&self, callback: impl FnOnce() + Send + 'static) {
+        self.queue.on_submitted_work_done(callback);
+    }
+
+    /// Submits a command encoder and waits until the GPU has finished executing it.
+    ///
+    /// On native platforms, this blocks until the submission has completed and the returned future is immediately ready. On the web, where blocking is not possible, the returned future resolves once the queue reports the work as done.
+    pub fn submit_and_wait(
+        &self,
+        encoder: wgpu::CommandEncoder,
+    ) -> impl std::future::Future<Output = ()> {
+        let submission_index = self.queue.submit(Some(encoder.finish()));
+        let (tx, rx) = oneshot::channel();
+        self.queue.on_submitted_work_done(move || tx.send(()));
+
+        // On the web, the browser drives completion for us.
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = self
+            .device
+            .poll(wgpu::Maintain::WaitForSubmissionIndex(submission_index));
+        #[cfg(target_arch = "wasm32")]
+        let _ = submission_index;
+
+        rx
+    }
+
+    /// Reads back a single texel of a texture, e.g. for object picking.
+    ///
+    /// The texture must have been created with [`wgpu::TextureUsages::COPY_SRC`] and have a copyable format. The returned bytes are in the texture's format.
+    ///
+    /// This submits a copy to the queue and resolves once it has been read back.
+    pub fn read_texel(
+        &self,
+        texture: &wgpu::Texture,
+        x: u32,
+        y: u32,
+    ) -> impl std::future::Future<Output = Vec<u8>> {
+        let bytes_per_texel = texture
+            .format()
+            .block_copy_size(None)
+            .expect("texture format is not copyable") as usize;
+
+        // Copies must be aligned to whole rows, so read back a padded row and only keep the texel.
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} texel readback", self.label_prefix)),
+            size: wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some(&format!("{} texel readback", self.label_prefix)),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let data = self.read_buffer(buffer);
+        async move {
+            let mut data = data.await;
+            data.truncate(bytes_per_texel);
+            data
+        }
+    }
+
+    /// Reads back the first mip level of a depth texture, e.g. to visualize it for debugging.
+    ///
+    /// wginit does not manage a depth texture itself, so this takes the texture to read. It must have the [`wgpu::TextureFormat::Depth32Float`] format and have been created with [`wgpu::TextureUsages::COPY_SRC`]. Other depth formats such as [`wgpu::TextureFormat::Depth24Plus`] cannot be copied, so use [`wgpu::TextureFormat::Depth32Float`] if readback is needed.
+    ///
+    /// The returned values are the raw values stored in the depth buffer, in row-major order. They are not linearized, as that depends on the projection used.
+    pub fn read_depth(
+        &self,
+        texture: &wgpu::Texture,
+    ) -> impl std::future::Future<Output = Vec<f32>> {
+        assert_eq!(
+            texture.format(),
+            wgpu::TextureFormat::Depth32Float,
+            "only Depth32Float textures can be read back"
+        );
+        assert!(
+            texture.usage().contains(wgpu::TextureUsages::COPY_SRC),
+            "depth textures must be created with COPY_SRC to be read back"
+        );
+
+        let width = texture.width();
+        let height = texture.height();
+        let unpadded_bytes_per_row = width * 4;
+        let bytes_per_row = unpadded_bytes_per_row.div_ceil(wgpu::COPY_BYTES_PER_ROW_ALIGNMENT)
+            * wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some(&format!("{} depth readback", self.label_prefix)),
+            size: bytes_per_row as u64 * height as u64,
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some(&format!("{} depth readback", self.label_prefix)),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::DepthOnly,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: Some(bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+        );
+        self.queue.submit(Some(encoder.finish()));
+
+        let data = self.read_buffer(buffer);
+        async move {
+            let data = data.await;
+            data.chunks_exact(bytes_per_row as usize)
+                .flat_map(|row| {
+                    row[..unpadded_bytes_per_row as usize]
+                        .chunks_exact(4)
+                        .map(|bytes| f32::from_ne_bytes(bytes.try_into().unwrap()))
+                })
+                .collect()
+        }
+    }
+
+    /// Maps a buffer with [`wgpu::BufferUsages::MAP_READ`] and reads back its contents.
+    fn read_buffer(&self, buffer: wgpu::Buffer) -> impl std::future::Future<Output = Vec<u8>> {
+        let (tx, rx) = oneshot::channel();
+        buffer
+            .slice(..)
+            .map_async(wgpu::MapMode::Read, move |result| tx.send(result));
+
+        // On the web, the browser drives mapping for us.
+        #[cfg(not(target_arch = "wasm32"))]
+        let _ = self.device.poll(wgpu::Maintain::Wait);
+
+        async move {
+            rx.await.expect("failed to map buffer");
+            let data = buffer.slice(..).get_mapped_range().to_vec();
+            buffer.unmap();
+            data
+        }
+    }
+
+    /// Creates a [`wgpu::Buffer`] initialized with the contents of a slice.
+    #[cfg(feature = "bytemuck")]
+    pub fn create_buffer_from_slice<T>(
+        &self,
+        label: Option<&str>,
+        contents: &[T],
+        usage: wgpu::BufferUsages,
+    ) -> wgpu::Buffer
+    where
+        T: bytemuck::Pod,
+    {
+        use wgpu::util::DeviceExt as _;
+        self.device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label,
+                contents: bytemuck::cast_slice(contents),
+                usage,
+            })
+    }
+
+    /// Generates a report of the GPU memory allocated by the device, e.g. to stay within a VRAM budget.
+    ///
+    /// This returns [`None`] on backends whose allocator does not support reports. At the time of writing, only DX12 supports them.
+    #[cfg(feature = "counters")]
+    pub fn memory_report(&self) -> Option<wgpu::AllocatorReport> {
+        self.device.generate_allocator_report()
+    }
+
+    /// Gets wgpu's internal resource counters, such as the number of live buffers and textures.
+    #[cfg(feature = "counters")]
+    pub fn internal_counters(&self) -> wgpu::InternalCounters {
+        self.device.get_internal_counters()
+    }
+
+    /// Creates an additional surface that renders with the same device, e.g. for tooltips or popups in windows not managed by wginit.
+    ///
+    /// The surface is configured with the adapter's default configuration for the given size. If the surface is not compatible with the adapter (e.g. because it belongs to a different display), [`AdditionalSurfaceError::Incompatible`] is returned. wginit does not manage the lifecycle of additional surfaces: they must be reconfigured on resize by the application, and must be dropped and recreated whenever wgpu is reinitialized (e.g. after [`ApplicationHandler::suspended`]).
+    pub fn create_additional_surface(
+        &self,
+        target: impl Into<wgpu::SurfaceTarget<'static>>,
+        size: winit::dpi::PhysicalSize<u32>,
+    ) -> Result<wgpu::Surface<'static>, AdditionalSurfaceError> {
+        let surface = self
+            .instance
+            .create_surface(target)
+            .map_err(AdditionalSurfaceError::CreateSurface)?;
+        let config = surface
+            .get_default_config(&self.adapter, size.width.max(1), size.height.max(1))
+            .ok_or(AdditionalSurfaceError::Incompatible)?;
+        surface.configure(&self.device, &config);
+        Ok(surface)
+    }
+
+    /// Starts a batch of uploads that is submitted separately from rendering work, e.g. for streaming assets.
+    ///
+    /// wgpu only has a single queue, so the batch is still submitted to [`Wgpu::queue`], but as its own submission that does not interleave with the render encoder.
+    pub fn upload_queue(&self) -> UploadQueue<'_> {
+        UploadQueue {
+            wgpu: self,
+            encoder: self
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some(&format!("{} uploads", self.label_prefix)),
+                }),
+        }
+    }
+}
+
+/// A batch of uploads created by [`Wgpu::upload_queue`].
+pub struct UploadQueue<'a> {
+    wgpu: &'a Wgpu,
+    encoder: wgpu::CommandEncoder,
+}
+
+impl UploadQueue<'_> {
+    /// Queues writing data to a buffer through a staging buffer.
+    ///
+    /// The buffer must have been created with [`wgpu::BufferUsages::COPY_DST`], and the offset and data length must be multiples of [`wgpu::COPY_BUFFER_ALIGNMENT`].
+    pub fn write_buffer(
+        &mut self,
+        buffer: &wgpu::Buffer,
+        offset: wgpu::BufferAddress,
+        data: &[u8],
+    ) {
+        use wgpu::util::DeviceExt as _;
+        // wgpu keeps the staging buffer alive until the copy has executed, so it can be dropped right away.
+        let staging = self
+            .wgpu
+            .device
+            .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some(&format!("{} upload staging", self.wgpu.label_prefix)),
+                contents: data,
+                usage: wgpu::BufferUsages::COPY_SRC,
+            });
+        self.encoder
+            .copy_buffer_to_buffer(&staging, 0, buffer, offset, data.len() as u64);
+    }
+
+    /// Gets the underlying command encoder, e.g. to record other copies into the batch.
+    pub fn encoder(&mut self) -> &mut wgpu::CommandEncoder {
+        &mut self.encoder
+    }
 
-        let adapter = instance
-            .request_adapter(&A::request_adapter_options(&surface))
-            .await
-            .expect("failed to find an appropriate adapter");
+    /// Submits the batch and returns a future that resolves once the GPU has finished executing it.
+    ///
+    /// Unlike [`Wgpu::submit_and_wait`], this does not block on native platforms. Instead, completion is detected when wginit polls the device (see [`Wgpu::on_submitted_work_done`]).
+    pub fn submit(self) -> impl std::future::Future<Output = ()> {
+        self.wgpu.queue.submit(Some(self.encoder.finish()));
+        let (tx, rx) = oneshot::channel();
+        self.wgpu.queue.on_submitted_work_done(move || tx.send(()));
+        rx
+    }
+}
 
-        let (device, queue) = adapter
-            .request_device(&A::device_descriptor(&adapter), None)
-            .await
-            .expect("failed to create device");
+/// Surface configuration changes requested at runtime via [`Context`].
+///
+/// These persist across resizes and suspensions.
+#[derive(Clone, Copy, Default)]
+struct SurfaceOverrides {
+    present_mode: Option<wgpu::PresentMode>,
+}
 
-        surface.configure(
-            &device,
-            &A::surface_configuration(&surface, &adapter, window.inner_size()),
-        );
+/// Builds the final [`wgpu::SurfaceConfiguration`] for a given window size.
+///
+/// [`ApplicationHandler::surface_configuration`] is called, runtime overrides are applied, and [`ApplicationHandler::adjust_surface_config`] gets the last say.
+///
+/// The requested present mode is returned alongside the configuration, as the override may not be supported.
+fn surface_configuration<A>(
+    surface: &wgpu::Surface,
+    adapter: &wgpu::Adapter,
+    size: winit::dpi::PhysicalSize<u32>,
+    overrides: SurfaceOverrides,
+) -> (wgpu::SurfaceConfiguration, wgpu::PresentMode)
+where
+    A: ApplicationHandler,
+{
+    let mut config = A::surface_configuration(surface, adapter, size);
+    if let Some(present_mode) = overrides.present_mode {
+        if surface
+            .get_capabilities(adapter)
+            .present_modes
+            .contains(&present_mode)
+        {
+            config.present_mode = present_mode;
+        }
+    }
+    A::adjust_surface_config(&mut config);
+    let requested_present_mode = overrides.present_mode.unwrap_or(config.present_mode);
+    (config, requested_present_mode)
+}
 
-        Self {
-            device,
-            queue,
-            adapter,
-            surface,
-            suspend_count,
+/// Checks that the surface and all of its view formats support the configured usage.
+fn validate_surface_usage(
+    surface: &wgpu::Surface,
+    adapter: &wgpu::Adapter,
+    config: &wgpu::SurfaceConfiguration,
+) -> Result<(), WgpuInitError> {
+    let unsupported = config.usage - surface.get_capabilities(adapter).usages;
+    if !unsupported.is_empty() {
+        return Err(WgpuInitError::UnsupportedSurfaceUsage {
+            format: config.format,
+            usage: unsupported,
+        });
+    }
+    for &format in std::iter::once(&config.format).chain(&config.view_formats) {
+        let unsupported = config.usage - adapter.get_texture_format_features(format).allowed_usages;
+        if !unsupported.is_empty() {
+            return Err(WgpuInitError::UnsupportedSurfaceUsage {
+                format,
+                usage: unsupported,
+            });
         }
     }
+    Ok(())
 }
 
-async fn new_wgpu_instance() -> wgpu::Instance {
-    // Taken from https://github.com/emilk/egui/blob/454abf705b87aba70cef582d6ce80f74aa398906/crates/eframe/src/web/web_painter_wgpu.rs#L117-L166
+async fn new_wgpu_instance<A>() -> Result<wgpu::Instance, WgpuInitError>
+where
+    A: ApplicationHandler,
+{
+    // Based on https://github.com/emilk/egui/blob/454abf705b87aba70cef582d6ce80f74aa398906/crates/eframe/src/web/web_painter_wgpu.rs#L117-L166
     //
-    // We try to see if we can use default backends first to initialize an adapter. If not, we fall back on GL.
-    let instance = wgpu::Instance::default();
-
-    if instance
-        .request_adapter(&wgpu::RequestAdapterOptions {
-            ..Default::default()
-        })
-        .await
-        .is_none()
-    {
-        wgpu::Instance::new(wgpu::InstanceDescriptor {
-            backends: wgpu::Backends::GL,
+    // We try each group of backends in order until one can initialize an adapter.
+    let require_webgpu = cfg!(target_arch = "wasm32") && A::require_webgpu();
+    let mut last_instance = None;
+    for &backends in A::backend_order() {
+        if require_webgpu && !backends.contains(wgpu::Backends::BROWSER_WEBGPU) {
+            continue;
+        }
+        let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
+            backends,
+            dx12_shader_compiler: A::dx12_shader_compiler(),
             ..Default::default()
-        })
-    } else {
-        instance
+        });
+        if instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                ..Default::default()
+            })
+            .await
+            .is_some()
+        {
+            return Ok(instance);
+        }
+        last_instance = Some(instance);
+    }
+    if require_webgpu {
+        return Err(WgpuInitError::WebGpuUnavailable);
     }
+    // Let the actual adapter request (which may e.g. force a fallback adapter) have the final say.
+    last_instance.ok_or(WgpuInitError::AdapterNotFound)
+}
+
+/// Runs a future to completion by blocking on it.
+#[cfg(not(target_arch = "wasm32"))]
+fn run_future(fut: impl std::future::Future<Output = ()>) {
+    pollster::block_on(fut);
+}
+
+/// Runs a future to completion in the background, as blocking is not possible on the web.
+#[cfg(target_arch = "wasm32")]
+fn run_future(fut: impl std::future::Future<Output = ()> + 'static) {
+    wasm_bindgen_futures::spawn_local(fut);
+}
+
+/// Lists the [`wgpu::AdapterInfo`] of all adapters on all backends.
+///
+/// This does not need a running event loop or window, so it can be used to e.g. build an adapter selection UI up front.
+///
+/// This is not available on the web, as adapters cannot be enumerated there.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn enumerate_adapters() -> Vec<wgpu::AdapterInfo> {
+    wgpu::Instance::new(wgpu::InstanceDescriptor {
+        backends: wgpu::Backends::all(),
+        ..Default::default()
+    })
+    .enumerate_adapters(wgpu::Backends::all())
+    .into_iter()
+    .map(|adapter| adapter.get_info())
+    .collect()
 }
 
 enum UserEvent<C> {
-    WgpuReady(Wgpu),
+    /// The result of initializing wgpu. Errors are tagged with the suspend count initialization started at, like [`Wgpu::suspend_count`].
+    WgpuReady(Result<Wgpu, (WgpuInitError, u64)>),
     Custom(C),
+    #[cfg(not(target_arch = "wasm32"))]
+    Wake,
+    #[cfg(any_thread_event_loop)]
+    Exit,
+}
+
+impl<C> UserEvent<C> {
+    /// Wraps the result of initializing wgpu that started at the given suspend count.
+    fn wgpu_ready(suspend_count: u64, result: Result<Wgpu, WgpuInitError>) -> Self {
+        Self::WgpuReady(result.map_err(|error| (error, suspend_count)))
+    }
 }
 
 /// Sender for user events.
-#[derive(Clone)]
-pub struct UserEventSender<C>(winit::event_loop::EventLoopProxy<UserEvent<C>>)
+///
+/// [`UserEventSender`] is only [`Send`] if the user event type is, so non-[`Send`] user events can still be sent from the event loop thread, but the compiler will reject moving the sender to another thread. If you intend to send events from other threads, make sure [`ApplicationHandler::UserEvent`] is [`Send`].
+///
+/// Once the application starts exiting (i.e. right before [`ApplicationHandler::exiting`] is called), the sender is closed and [`UserEventSender::send_event`] will fail with [`winit::event_loop::EventLoopClosed`]. Background producers should check [`UserEventSender::is_closed`] or handle the error instead of unwrapping it, so they can stop cleanly during shutdown.
+///
+/// Sending an event from the event loop thread itself (e.g. from [`ApplicationHandler::window_event`]) is a supported way to defer work to a later event loop iteration, e.g. to avoid re-entrancy or to batch work. The event is queued and delivered to [`ApplicationHandler::user_event`] after the current event has been handled, and sending always wakes up the event loop, even with [`winit::event_loop::ControlFlow::Wait`], so no redraw needs to be requested to make progress.
+pub struct UserEventSender<C>
 where
-    C: 'static;
+    C: 'static,
+{
+    proxy: winit::event_loop::EventLoopProxy<UserEvent<C>>,
+    closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl<C> Clone for UserEventSender<C>
+where
+    C: 'static,
+{
+    fn clone(&self) -> Self {
+        Self {
+            proxy: self.proxy.clone(),
+            closed: self.closed.clone(),
+        }
+    }
+}
 
 impl<C> UserEventSender<C>
 where
     C: 'static,
 {
+    fn new(event_loop: &winit::event_loop::EventLoop<UserEvent<C>>) -> Self {
+        Self {
+            proxy: event_loop.create_proxy(),
+            closed: std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false)),
+        }
+    }
+
+    /// Returns if the event loop is exiting or has exited, and events can no longer be sent.
+    pub fn is_closed(&self) -> bool {
+        self.closed.load(std::sync::atomic::Ordering::Acquire)
+    }
+
+    /// Converts this sender into a callback that only wakes up the event loop.
+    ///
+    /// This is useful for integrating async runtimes that need a wake callback. Waking the event loop does not deliver a user event, but [`ApplicationHandler::about_to_wait`] will run again.
+    ///
+    /// This is not available on the web, as wgpu state is not [`Send`] there.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn into_waker(self) -> impl Fn() + Send + Sync + Clone
+    where
+        C: Send,
+    {
+        let proxy = self.proxy;
+        move || {
+            let _ = proxy.send_event(UserEvent::Wake);
+        }
+    }
+
     /// Sends a user event to the application.
     pub fn send_event(&self, event: C) -> Result<(), winit::event_loop::EventLoopClosed<C>> {
-        self.0.send_event(UserEvent::Custom(event)).map_err(|e| {
-            let UserEvent::Custom(e) = e.0 else {
-                unreachable!()
-            };
-            winit::event_loop::EventLoopClosed(e)
-        })
+        if self.is_closed() {
+            return Err(winit::event_loop::EventLoopClosed(event));
+        }
+        self.proxy
+            .send_event(UserEvent::Custom(event))
+            .map_err(|e| {
+                let UserEvent::Custom(e) = e.0 else {
+                    unreachable!()
+                };
+                winit::event_loop::EventLoopClosed(e)
+            })
+    }
+
+    /// Runs a future in the background and sends its mapped result as a user event, e.g. to load an asset without blocking the event loop.
+    ///
+    /// The future runs on a small thread pool shared by the whole application, so it and its output must be [`Send`]. If the event loop has exited by the time the future completes, the result is dropped. If the future or the mapping panics, no event is sent, but the pool keeps running other futures.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn spawn<F, T>(&self, fut: F, map: impl FnOnce(T) -> C + Send + 'static)
+    where
+        F: std::future::Future<Output = T> + Send + 'static,
+        C: Send,
+    {
+        let sender = self.clone();
+        pool::spawn(async move {
+            let _ = sender.send_event(map(fut.await));
+        });
+    }
+
+    /// Runs a future in the background and sends its mapped result as a user event, e.g. to load an asset without blocking the event loop.
+    ///
+    /// On the web, the future runs on the main thread via [`wasm_bindgen_futures::spawn_local`], so it does not need to be [`Send`]. If the event loop has exited by the time the future completes, the result is dropped.
+    #[cfg(target_arch = "wasm32")]
+    pub fn spawn<F, T>(&self, fut: F, map: impl FnOnce(T) -> C + 'static)
+    where
+        F: std::future::Future<Output = T> + 'static,
+    {
+        let sender = self.clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let _ = sender.send_event(map(fut.await));
+        });
+    }
+}
+
+/// A user event that knows how to handle itself.
+///
+/// This is an optional helper over [`ApplicationHandler::user_event`]: instead of matching on one large enum, each command encapsulates its own handling. To use it, set [`ApplicationHandler::UserEvent`] to `Box<dyn UserCommand<Self> + Send>` and dispatch from [`ApplicationHandler::user_event`]. The [`Send`] bound makes the [`UserEventSender`] [`Send`] too, so commands can be sent from background threads; it can be left out if all commands are sent from the event loop thread.
+///
+/// ```
+/// struct App {
+///     scene: Option<String>,
+/// }
+///
+/// struct SceneLoaded(String);
+///
+/// impl wginit::UserCommand<App> for SceneLoaded {
+///     fn handle(self: Box<Self>, _ctxt: &wginit::Context, app: &mut App) {
+///         app.scene = Some(self.0);
+///     }
+/// }
+///
+/// impl wginit::ApplicationHandler for App {
+///     type UserEvent = Box<dyn wginit::UserCommand<Self> + Send>;
+///
+///     fn new(user_event_sender: wginit::UserEventSender<Self::UserEvent>) -> Self {
+///         std::thread::spawn(move || {
+///             let scene = String::from("a large scene");
+///             let _ = user_event_sender.send_event(Box::new(SceneLoaded(scene)));
+///         });
+///         Self { scene: None }
+///     }
+///
+///     fn user_event(&mut self, ctxt: &wginit::Context, event: Self::UserEvent) {
+///         event.handle(ctxt, self);
+///     }
+/// }
+/// ```
+pub trait UserCommand<A> {
+    /// Handles this command.
+    fn handle(self: Box<Self>, ctxt: &Context, app: &mut A);
+}
+
+impl<A, F> UserCommand<A> for F
+where
+    F: FnOnce(&Context, &mut A),
+{
+    fn handle(self: Box<Self>, ctxt: &Context, app: &mut A) {
+        self(ctxt, app)
+    }
+}
+
+/// State owned by [`WinitApplicationHandler`] that is exposed through [`Context`].
+#[derive(Default)]
+struct SharedState {
+    lifecycle: lifecycle::Lifecycle,
+    create_window_requested: std::cell::Cell<bool>,
+    surface_overrides: std::cell::Cell<SurfaceOverrides>,
+    reconfigure_requested: std::cell::Cell<bool>,
+    reinitialize_requested: std::cell::Cell<bool>,
+    continuous_tokens: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    data: std::cell::RefCell<Option<Box<dyn std::any::Any>>>,
+    redraw_pending: std::cell::Cell<bool>,
+    /// Whether the window is hidden until the first frame has been rendered.
+    ///
+    /// Meanwhile, redraws are driven directly instead of being requested from winit, as some platforms (e.g. Windows) do not deliver redraw requests to hidden windows, and those that do would otherwise render twice.
+    show_after_redraw: std::cell::Cell<bool>,
+    #[cfg(feature = "input")]
+    input: input::InputState,
+}
+
+impl SharedState {
+    /// Requests a redraw, unless one is already pending.
+    fn request_redraw(&self, window: &winit::window::Window) {
+        if !self.redraw_pending.replace(true) && !self.show_after_redraw.get() {
+            window.request_redraw();
+        }
     }
 }
 
@@ -144,23 +1480,286 @@ where
     app: A,
     window: Option<std::sync::Arc<winit::window::Window>>,
     wgpu: Option<Wgpu>,
-    suspend_count: u64,
+    shared: SharedState,
+    resumed: bool,
+    current_monitor: Option<winit::monitor::MonitorHandle>,
+    continuous: bool,
+    first_frame_retries: Option<u32>,
+    wgpu_errors: WgpuErrors,
+    frame_index: u64,
     event_loop_proxy: winit::event_loop::EventLoopProxy<UserEvent<A::UserEvent>>,
+    closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    /// Finishes wgpu initialization elsewhere (e.g. on a tokio runtime) instead of blocking the event loop thread on it.
+    #[cfg(not(target_arch = "wasm32"))]
+    spawner: Option<Spawner>,
+}
+
+/// wgpu initialization for a window that has gotten as far as creating the instance and surface, which some platforms (e.g. macOS and iOS) require to happen on the event loop thread.
+///
+/// The rest is finished via [`Wgpu::from_surface`].
+struct PendingWgpu {
+    start: web_time::Instant,
+    instance: wgpu::Instance,
+    surface: wgpu::Surface<'static>,
+    window: std::sync::Arc<winit::window::Window>,
+    suspend_count: u64,
+    overrides: SurfaceOverrides,
+    errors: Option<WgpuErrors>,
 }
 
+/// Finishes wgpu initialization, delivering the result as [`UserEvent::WgpuReady`].
+type Spawner = Box<dyn Fn(PendingWgpu)>;
+
+/// wgpu errors along with the index of the frame they were captured in, if any, collected until they are delivered via [`ApplicationHandler::wgpu_error`].
+type WgpuErrors = std::sync::Arc<std::sync::Mutex<Vec<(wgpu::Error, Option<u64>)>>>;
+
 impl<A> WinitApplicationHandler<A>
 where
     A: ApplicationHandler,
 {
-    fn new(app: A, event_loop: &winit::event_loop::EventLoop<UserEvent<A::UserEvent>>) -> Self {
+    fn new(
+        app: A,
+        event_loop: &winit::event_loop::EventLoop<UserEvent<A::UserEvent>>,
+        closed: std::sync::Arc<std::sync::atomic::AtomicBool>,
+        spawner: Option<Spawner>,
+    ) -> Self {
+        // Initialization is already asynchronous on the web, so there is nothing to spawn.
+        #[cfg(target_arch = "wasm32")]
+        let _ = spawner;
+
         Self {
             app,
             window: None,
             wgpu: None,
-            suspend_count: 0,
+            shared: SharedState {
+                data: std::cell::RefCell::new(A::create_shared_data()),
+                ..Default::default()
+            },
+            resumed: false,
+            current_monitor: None,
+            continuous: false,
+            first_frame_retries: None,
+            wgpu_errors: Default::default(),
+            frame_index: 0,
             event_loop_proxy: event_loop.create_proxy(),
+            closed,
+            #[cfg(not(target_arch = "wasm32"))]
+            spawner,
+        }
+    }
+
+    /// Creates the window if needed and starts initializing wgpu.
+    fn resume(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        self.shared.create_window_requested.set(false);
+
+        if self.window.is_none() {
+            let mut window_attrs = A::window_attrs();
+            if A::show_after_first_frame() {
+                window_attrs = window_attrs.with_visible(false);
+                self.shared.show_after_redraw.set(true);
+            }
+            let window = event_loop
+                .create_window(window_attrs)
+                .expect("failed to create window");
+            self.current_monitor = window.current_monitor();
+            self.window = Some(std::sync::Arc::new(window));
+        }
+
+        let size = self.window.as_ref().unwrap().inner_size();
+        if self.shared.lifecycle.window_ready(size) {
+            self.init_wgpu();
+        }
+    }
+
+    /// Starts initializing wgpu, which will be delivered via [`UserEvent::WgpuReady`].
+    fn init_wgpu(&mut self) {
+        let window = self.window.clone().unwrap();
+
+        let event_loop_proxy = self.event_loop_proxy.clone();
+        let suspend_count = self.shared.lifecycle.suspend_count();
+        let surface_overrides = self.shared.surface_overrides.get();
+        let wgpu_errors = Some(self.wgpu_errors.clone());
+
+        #[cfg(not(target_arch = "wasm32"))]
+        {
+            // The instance and surface are created here on the event loop thread, as some platforms (e.g. macOS and iOS) require it. This blocks on probing an adapter for each backend group in turn, which natively completes without waiting on anything asynchronous.
+            let start = web_time::Instant::now();
+            let pending = match pollster::block_on(new_wgpu_instance::<A>()).and_then(|instance| {
+                let surface = instance
+                    .create_surface(window.clone())
+                    .map_err(WgpuInitError::CreateSurface)?;
+                Ok(PendingWgpu {
+                    start,
+                    instance,
+                    surface,
+                    window,
+                    suspend_count,
+                    overrides: surface_overrides,
+                    errors: wgpu_errors,
+                })
+            }) {
+                Ok(pending) => pending,
+                Err(e) => {
+                    assert!(event_loop_proxy
+                        .send_event(UserEvent::wgpu_ready(suspend_count, Err(e)))
+                        .is_ok());
+                    return;
+                }
+            };
+
+            if let Some(spawner) = self.spawner.as_ref() {
+                spawner(pending);
+                return;
+            }
+            run_future(async move {
+                assert!(event_loop_proxy
+                    .send_event(UserEvent::wgpu_ready(
+                        suspend_count,
+                        Wgpu::from_surface::<A>(pending).await
+                    ))
+                    .is_ok());
+            });
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            let init = A::wrap_init_future(Box::pin(Wgpu::new::<A>(
+                window,
+                suspend_count,
+                surface_overrides,
+                wgpu_errors,
+            )));
+            run_future(async move {
+                assert!(event_loop_proxy
+                    .send_event(UserEvent::wgpu_ready(suspend_count, init.await))
+                    .is_ok());
+            });
+        }
+    }
+
+    /// Destroys the window and wgpu state without exiting, so the window can be created again via [`Context::create_window`].
+    fn close_window(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        // This goes through the same steps as a suspension, so the application drops its GPU resources.
+        self.shared.lifecycle.close();
+        self.wgpu = None;
+        self.app.suspended(&Context::new(
+            event_loop,
+            self.window.as_ref().map(|window| window.as_ref()),
+            self.wgpu.as_ref(),
+            &self.shared,
+        ));
+        self.window = None;
+        self.current_monitor = None;
+        self.shared.redraw_pending.set(false);
+    }
+
+    /// Dispatches a gesture to [`ApplicationHandler::gesture`].
+    fn dispatch_gesture(
+        &mut self,
+        event_loop: &winit::event_loop::ActiveEventLoop,
+        gesture: GestureEvent,
+    ) {
+        self.app.gesture(
+            &Context::new(
+                event_loop,
+                self.window.as_ref().map(|window| window.as_ref()),
+                self.wgpu.as_ref(),
+                &self.shared,
+            ),
+            gesture,
+        );
+    }
+
+    /// Tears down wgpu and the window once the event loop has stopped, returning the application.
+    ///
+    /// wgpu is dropped before the window, as the surface references it. [`Wgpu`] itself drops the surface before the device.
+    fn shutdown(mut self) -> A {
+        self.wgpu = None;
+        self.window = None;
+        self.app
+    }
+
+    /// Calls [`ApplicationHandler::redraw`] and handles its outcome.
+    fn redraw(&mut self) {
+        let (Some(window), Some(wgpu)) = (self.window.as_ref(), self.wgpu.as_ref()) else {
+            return;
+        };
+        // Acquiring a texture from an unconfigured surface panics on some backends.
+        if !wgpu.surface_configured {
+            return;
+        }
+        let capture_frame_errors = A::capture_frame_errors();
+        if capture_frame_errors {
+            wgpu.device.push_error_scope(wgpu::ErrorFilter::Validation);
+        }
+
+        let frame_index = self.frame_index;
+        self.frame_index += 1;
+        let start = web_time::Instant::now();
+        let result = self.app.redraw(window, wgpu);
+        if let Some(budget) = A::frame_budget() {
+            let actual = start.elapsed();
+            if actual > budget {
+                self.app.frame_overrun(actual, budget, frame_index);
+            }
+        }
+
+        if capture_frame_errors {
+            let error = wgpu.device.pop_error_scope();
+            let wgpu_errors = self.wgpu_errors.clone();
+            run_future(async move {
+                if let Some(error) = error.await {
+                    wgpu_errors.lock().unwrap().push((error, Some(frame_index)));
+                }
+            });
+        }
+        match result {
+            Ok(()) => {
+                self.first_frame_retries = None;
+                if self.shared.show_after_redraw.replace(false) {
+                    window.set_visible(true);
+                }
+            }
+            Err(wgpu::SurfaceError::Lost | wgpu::SurfaceError::Outdated) => {
+                // If the application manages the surface, the frame is skipped: redrawing before it reconfigures the surface would just fail again.
+                if A::manage_surface() {
+                    if let Some(wgpu) = self.wgpu.as_mut() {
+                        wgpu.surface_configured = false;
+                    }
+                    // This will also request another redraw once the surface is reconfigured.
+                    self.shared.reconfigure_requested.set(true);
+                }
+            }
+            Err(wgpu::SurfaceError::Timeout) => match self.first_frame_retries {
+                // Some drivers are not ready to present right after initialization, which reconfiguring usually fixes.
+                Some(retries) if retries > 0 && A::manage_surface() => {
+                    self.first_frame_retries = Some(retries - 1);
+                    self.shared.reconfigure_requested.set(true);
+                }
+                _ => {
+                    self.first_frame_retries = None;
+                    self.shared.request_redraw(window);
+                }
+            },
+            Err(wgpu::SurfaceError::OutOfMemory) => {
+                panic!("out of memory while acquiring surface texture");
+            }
+        }
+
+        // Requesting the next redraw only after presenting lets the compositor pace continuous rendering (e.g. via frame callbacks on Wayland) instead of spinning.
+        self.continuous = self.is_continuous();
+        if self.continuous {
+            self.shared.request_redraw(window);
         }
     }
+
+    /// Returns if any [`ContinuousToken`] is alive.
+    fn is_continuous(&self) -> bool {
+        self.shared
+            .continuous_tokens
+            .load(std::sync::atomic::Ordering::Relaxed)
+            > 0
+    }
 }
 
 impl<A> winit::application::ApplicationHandler<UserEvent<A::UserEvent>>
@@ -169,48 +1768,38 @@ where
     A: ApplicationHandler,
 {
     fn resumed(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        let window = self
-            .window
-            .get_or_insert_with(|| {
-                std::sync::Arc::new(
-                    event_loop
-                        .create_window(A::window_attrs())
-                        .expect("failed to create window"),
-                )
-            })
-            .clone();
-
-        let event_loop_proxy = self.event_loop_proxy.clone();
-        let fut = async move {
-            assert!(event_loop_proxy
-                .send_event(UserEvent::WgpuReady(
-                    Wgpu::new::<A>(window, self.suspend_count).await
-                ))
-                .is_ok());
-        };
-
-        #[cfg(not(target_arch = "wasm32"))]
-        pollster::block_on(fut);
-
-        #[cfg(target_arch = "wasm32")]
-        wasm_bindgen_futures::spawn_local(fut);
+        self.resumed = true;
+        if self.window.is_none()
+            && A::defer_window_creation()
+            && !self.shared.create_window_requested.get()
+        {
+            return;
+        }
+        self.resume(event_loop);
     }
 
     fn suspended(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        self.resumed = false;
+        self.shared.lifecycle.suspend();
         self.wgpu = None;
-        self.suspend_count += 1;
+        // The native window may go away (e.g. on Android) before a requested redraw is delivered.
+        self.shared.redraw_pending.set(false);
         self.app.suspended(&Context::new(
             event_loop,
             self.window.as_ref().map(|window| window.as_ref()),
             self.wgpu.as_ref(),
+            &self.shared,
         ));
     }
 
     fn exiting(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
+        self.closed
+            .store(true, std::sync::atomic::Ordering::Release);
         self.app.exiting(&Context::new(
             event_loop,
             self.window.as_ref().map(|window| window.as_ref()),
             self.wgpu.as_ref(),
+            &self.shared,
         ));
     }
 
@@ -219,7 +1808,11 @@ where
             event_loop,
             self.window.as_ref().map(|window| window.as_ref()),
             self.wgpu.as_ref(),
+            &self.shared,
         ));
+        if let Some(wgpu) = self.wgpu.as_ref() {
+            self.app.trim_memory(wgpu);
+        }
     }
 
     fn new_events(
@@ -227,41 +1820,209 @@ where
         event_loop: &winit::event_loop::ActiveEventLoop,
         start_cause: winit::event::StartCause,
     ) {
-        self.app.new_events(event_loop, start_cause);
+        if matches!(start_cause, winit::event::StartCause::Init) {
+            event_loop.set_control_flow(A::initial_control_flow());
+        }
+        self.app.new_events(
+            &Context::new(
+                event_loop,
+                self.window.as_ref().map(|window| window.as_ref()),
+                self.wgpu.as_ref(),
+                &self.shared,
+            ),
+            start_cause,
+        );
     }
 
     fn about_to_wait(&mut self, event_loop: &winit::event_loop::ActiveEventLoop) {
-        self.app.about_to_wait(&Context::new(
+        // Drive callbacks such as Wgpu::on_submitted_work_done and buffer mapping. On the web, the browser does this for us.
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(wgpu) = self.wgpu.as_ref() {
+            let _ = wgpu.device.poll(wgpu::Maintain::Poll);
+        }
+
+        // Handle device loss before anything else gets to use the lost device.
+        let lost = self
+            .wgpu
+            .as_ref()
+            .is_some_and(|wgpu| wgpu.lost.load(std::sync::atomic::Ordering::Acquire));
+        if lost && self.shared.lifecycle.device_lost() {
+            // Errors reported by the lost device are only fallout of the loss, which would otherwise panic in the default ApplicationHandler::wgpu_error.
+            self.wgpu_errors.lock().unwrap().clear();
+            // Rebuild wgpu from scratch, which requests a (possibly different) adapter again.
+            self.wgpu = None;
+            self.app.adapter_lost(&Context::new(
+                event_loop,
+                self.window.as_ref().map(|window| window.as_ref()),
+                self.wgpu.as_ref(),
+                &self.shared,
+            ));
+            self.init_wgpu();
+        }
+
+        let wgpu_errors = std::mem::take(&mut *self.wgpu_errors.lock().unwrap());
+        for (error, frame_index) in wgpu_errors {
+            self.app.wgpu_error(
+                &Context::new(
+                    event_loop,
+                    self.window.as_ref().map(|window| window.as_ref()),
+                    self.wgpu.as_ref(),
+                    &self.shared,
+                ),
+                error,
+                frame_index,
+            );
+        }
+
+        let ctxt = Context::new(
             event_loop,
             self.window.as_ref().map(|window| window.as_ref()),
             self.wgpu.as_ref(),
-        ));
+            &self.shared,
+        );
+        self.app.about_to_wait(&ctxt);
+        event_loop.set_control_flow(self.app.next_control_flow(&ctxt));
+
+        // Kick off continuous rendering. From then on, every redraw requests the next one.
+        if !self.continuous && self.is_continuous() {
+            if let Some(window) = self.window.as_ref() {
+                self.continuous = true;
+                self.shared.request_redraw(window);
+            }
+        }
+
+        if self.resumed && self.window.is_none() && self.shared.create_window_requested.get() {
+            self.resume(event_loop);
+        }
+
+        if self.shared.reinitialize_requested.take()
+            && self.wgpu.is_some()
+            && self.shared.lifecycle.reinitialize()
+        {
+            // This reuses the suspension machinery: bumping the suspend count makes sure a stale initialization is ignored.
+            self.wgpu = None;
+            self.init_wgpu();
+        }
+
+        if self.shared.reconfigure_requested.take() {
+            if let (Some(window), lifecycle::LifecycleState::Running, Some(wgpu)) = (
+                self.window.as_ref(),
+                self.shared.lifecycle.state(),
+                self.wgpu.as_mut(),
+            ) {
+                wgpu.configure_surface::<A>(
+                    window.inner_size(),
+                    self.shared.surface_overrides.get(),
+                );
+                self.shared.request_redraw(window);
+            }
+        }
+
+        // Keep rendering directly until the hidden window could be shown, e.g. when the first frame had to be retried.
+        if self.shared.show_after_redraw.get() && self.shared.redraw_pending.take() {
+            self.redraw();
+        }
     }
 
     fn window_event(
         &mut self,
         event_loop: &winit::event_loop::ActiveEventLoop,
         _window_id: winit::window::WindowId,
-        event: winit::event::WindowEvent,
+        mut event: winit::event::WindowEvent,
     ) {
+        // Events may still trickle in for a window that was closed via ApplicationHandler::keep_running_after_close.
+        if self.window.is_none() {
+            return;
+        }
+
+        #[cfg(feature = "input")]
+        let redraw_requested = matches!(event, winit::event::WindowEvent::RedrawRequested);
+        let close_requested = matches!(event, winit::event::WindowEvent::CloseRequested);
+        #[cfg(feature = "input")]
+        self.shared.input.handle_window_event(&event);
+
         match event {
             winit::event::WindowEvent::Resized(size) => {
+                if self.shared.lifecycle.resized(size) {
+                    self.init_wgpu();
+                }
+
                 let window = self.window.as_ref().unwrap();
-                let Some(wgpu) = self.wgpu.as_ref() else {
+                let (lifecycle::LifecycleState::Running, Some(wgpu)) =
+                    (self.shared.lifecycle.state(), self.wgpu.as_mut())
+                else {
                     return;
                 };
-                wgpu.surface.configure(
-                    &wgpu.device,
-                    &A::surface_configuration(&wgpu.surface, &wgpu.adapter, size),
+                wgpu.configure_surface::<A>(size, self.shared.surface_overrides.get());
+                self.app.surface_resized(
+                    &Context::new(
+                        event_loop,
+                        Some(window.as_ref()),
+                        self.wgpu.as_ref(),
+                        &self.shared,
+                    ),
+                    size,
+                );
+                if A::render_during_resize() {
+                    self.redraw();
+                } else if A::redraw_on_resize() {
+                    self.shared.request_redraw(window);
+                }
+            }
+            winit::event::WindowEvent::PinchGesture { delta, phase, .. } => {
+                self.dispatch_gesture(event_loop, GestureEvent::Pinch { delta, phase });
+            }
+            winit::event::WindowEvent::RotationGesture { delta, phase, .. } => {
+                self.dispatch_gesture(
+                    event_loop,
+                    GestureEvent::Rotation {
+                        delta: delta.to_radians(),
+                        phase,
+                    },
+                );
+            }
+            winit::event::WindowEvent::PanGesture { delta, phase, .. } => {
+                self.dispatch_gesture(event_loop, GestureEvent::Pan { delta, phase });
+            }
+            winit::event::WindowEvent::ScaleFactorChanged {
+                scale_factor,
+                ref mut inner_size_writer,
+            } => {
+                self.app.scale_factor_changed(
+                    &Context::new(
+                        event_loop,
+                        self.window.as_ref().map(|window| window.as_ref()),
+                        self.wgpu.as_ref(),
+                        &self.shared,
+                    ),
+                    scale_factor,
+                    inner_size_writer,
                 );
-                window.request_redraw();
             }
-            winit::event::WindowEvent::RedrawRequested => {
+            winit::event::WindowEvent::Moved(_) => {
+                // Only fire when the monitor actually changes, not on every move within the same monitor.
                 let window = self.window.as_ref().unwrap();
-                let Some(wgpu) = self.wgpu.as_ref() else {
+                let monitor = window.current_monitor();
+                if monitor != self.current_monitor {
+                    self.current_monitor = monitor.clone();
+                    self.app.monitor_changed(
+                        &Context::new(
+                            event_loop,
+                            Some(window.as_ref()),
+                            self.wgpu.as_ref(),
+                            &self.shared,
+                        ),
+                        monitor,
+                    );
+                }
+            }
+            winit::event::WindowEvent::RedrawRequested => {
+                self.shared.redraw_pending.set(false);
+                // Never redraw between suspended and the next WgpuReady.
+                if !self.shared.lifecycle.can_redraw() || self.wgpu.is_none() {
                     return;
-                };
-                self.app.redraw(window, wgpu);
+                }
+                self.redraw();
             }
             _ => {}
         };
@@ -271,9 +2032,19 @@ where
                 event_loop,
                 self.window.as_ref().map(|window| window.as_ref()),
                 self.wgpu.as_ref(),
+                &self.shared,
             ),
             event,
         );
+
+        #[cfg(feature = "input")]
+        if redraw_requested {
+            self.shared.input.end_frame();
+        }
+
+        if close_requested && A::keep_running_after_close() {
+            self.close_window(event_loop);
+        }
     }
 
     fn user_event(
@@ -282,7 +2053,29 @@ where
         event: UserEvent<A::UserEvent>,
     ) {
         match event {
-            UserEvent::WgpuReady(wgpu) => {
+            UserEvent::WgpuReady(Err((error, suspend_count))) => {
+                // As with success below, a failure of an initialization that was superseded is stale.
+                if !self.shared.lifecycle.wgpu_failed(suspend_count) {
+                    return;
+                }
+                self.app.wgpu_init_failed(
+                    &Context::new(
+                        event_loop,
+                        self.window.as_ref().map(|window| window.as_ref()),
+                        None,
+                        &self.shared,
+                    ),
+                    error,
+                );
+            }
+            UserEvent::WgpuReady(Ok(wgpu)) => {
+                // If we were suspended again while wgpu was initializing (e.g. on the web, where initialization is asynchronous), this wgpu state is stale.
+                if !self.shared.lifecycle.wgpu_ready(wgpu.suspend_count) {
+                    return;
+                }
+                // A redraw requested before wgpu was torn down may never have been delivered, which would otherwise swallow every further request.
+                self.shared.redraw_pending.set(false);
+
                 // We can just unwrap here because if we're getting the wgpu state we can safely assume the window is already initialized, otherwise we have bigger problems.
                 let window = self.window.as_ref().unwrap();
                 self.wgpu = Some(wgpu);
@@ -290,19 +2083,35 @@ where
                     event_loop,
                     Some(window.as_ref()),
                     self.wgpu.as_ref(),
+                    &self.shared,
                 ));
-                window.request_redraw();
+                self.app.prewarm(self.wgpu.as_ref().unwrap());
+                self.first_frame_retries = Some(A::first_frame_retries());
+                if self.shared.show_after_redraw.get() {
+                    // Render the first frame right away, which also covers any redraw requested from ApplicationHandler::resumed.
+                    self.shared.redraw_pending.set(false);
+                    self.redraw();
+                } else {
+                    self.shared.request_redraw(window);
+                }
             }
             UserEvent::Custom(e) => {
-                self.app.user_event(
-                    &Context::new(
-                        event_loop,
-                        self.window.as_ref().map(|window| window.as_ref()),
-                        self.wgpu.as_ref(),
-                    ),
-                    e,
+                let ctxt = Context::new(
+                    event_loop,
+                    self.window.as_ref().map(|window| window.as_ref()),
+                    self.wgpu.as_ref(),
+                    &self.shared,
                 );
+                if let Some(e) = self.app.pre_user_event(&ctxt, e) {
+                    self.app.user_event(&ctxt, e);
+                }
+            }
+            #[cfg(any_thread_event_loop)]
+            UserEvent::Exit => {
+                event_loop.exit();
             }
+            #[cfg(not(target_arch = "wasm32"))]
+            UserEvent::Wake => {}
         }
     }
 
@@ -317,6 +2126,7 @@ where
                 event_loop,
                 self.window.as_ref().map(|window| window.as_ref()),
                 self.wgpu.as_ref(),
+                &self.shared,
             ),
             device_id,
             event,
@@ -338,41 +2148,275 @@ where
     /// If no user events are desired, you can use [`std::convert::Infallible`] for the type.
     type UserEvent;
 
+    /// Creates data that is managed by wginit and shared with all handlers via [`Context::data`] and [`Context::data_mut`].
+    ///
+    /// This is an alternative to storing shared state such as asset managers or configuration on the application itself. It is created once, right after [`ApplicationHandler::new`].
+    fn create_shared_data() -> Option<Box<dyn std::any::Any>> {
+        None
+    }
+
+    /// Configures the event loop before it is built, e.g. to force X11 or Wayland on Linux via [`winit::platform::x11::EventLoopBuilderExtX11`] or [`winit::platform::wayland::EventLoopBuilderExtWayland`].
+    ///
+    /// The user event type `T` of the builder is internal to wginit, so the builder can only be configured via methods that are generic over it, which includes all the platform extension traits.
+    fn configure_event_loop<T>(builder: &mut winit::event_loop::EventLoopBuilder<T>) {
+        let _ = builder;
+    }
+
     /// Gets the window attributes for creating a window for this application.
+    ///
+    /// By default, this is composed from the more granular hooks such as [`ApplicationHandler::title`] and [`ApplicationHandler::resizable`]. If this is overridden, those hooks are ignored.
     fn window_attrs() -> winit::window::WindowAttributes {
-        #[allow(unused_mut)]
-        let mut window_attrs = winit::window::WindowAttributes::default();
+        let mut window_attrs = winit::window::WindowAttributes::default()
+            .with_title(Self::title())
+            .with_resizable(Self::resizable())
+            .with_decorations(Self::decorated())
+            .with_maximized(Self::maximized());
         #[cfg(target_arch = "wasm32")]
         {
             use winit::platform::web::WindowAttributesExtWebSys as _;
             window_attrs = window_attrs.with_append(true);
         }
-        window_attrs
+        if let Some(min_inner_size) = Self::min_inner_size() {
+            window_attrs = window_attrs.with_min_inner_size(min_inner_size);
+        }
+        if let Some(max_inner_size) = Self::max_inner_size() {
+            window_attrs = window_attrs.with_max_inner_size(max_inner_size);
+        }
+        window_attrs.with_window_level(Self::window_level())
+    }
+
+    /// Gets the title of the window.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`].
+    fn title() -> String {
+        winit::window::WindowAttributes::default().title
+    }
+
+    /// Whether the window should be resizable by the user.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`].
+    fn resizable() -> bool {
+        true
+    }
+
+    /// Whether the window should have decorations, such as a title bar and borders.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`].
+    fn decorated() -> bool {
+        true
+    }
+
+    /// Whether the window should be created maximized.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`].
+    fn maximized() -> bool {
+        false
+    }
+
+    /// Gets the minimum inner size of the window, if any.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`]. Some platforms (e.g. tiling Wayland compositors, the web and mobile) do not enforce it. The surface always follows the actual window size, as a surface that does not match the window is rejected by some backends (such as Vulkan when the surface extent is fixed), so layouts must be able to cope with smaller sizes.
+    fn min_inner_size() -> Option<winit::dpi::Size> {
+        None
+    }
+
+    /// Gets the maximum inner size of the window, if any.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`]. As with [`ApplicationHandler::min_inner_size`], the surface always follows the actual window size, which may exceed this on platforms that do not enforce it.
+    fn max_inner_size() -> Option<winit::dpi::Size> {
+        None
+    }
+
+    /// Gets the [`winit::window::WindowLevel`] of the window, e.g. to keep overlay windows on top of others.
+    ///
+    /// This is applied by the default [`ApplicationHandler::window_attrs`], and is ignored on platforms where window levels are not supported. It can be changed later with [`Context::set_always_on_top`].
+    fn window_level() -> winit::window::WindowLevel {
+        winit::window::WindowLevel::Normal
+    }
+
+    /// Whether the window should be created hidden and only shown after the first [`ApplicationHandler::redraw`].
+    ///
+    /// This avoids a flash of unrendered window contents on startup. On platforms where window visibility cannot be changed, the window is just left visible.
+    ///
+    /// As some platforms (e.g. Windows) do not deliver [`winit::event::WindowEvent::RedrawRequested`] to hidden windows, the first frame is rendered right after [`ApplicationHandler::prewarm`] instead of waiting for a redraw request. The window is only shown once a redraw has succeeded, so redraws are retried until then (see [`ApplicationHandler::first_frame_retries`]).
+    ///
+    /// winit cannot fill a window without a renderer, so this is the recommended way to avoid showing undefined contents (often black or garbage) while wgpu initializes. To present a solid color as soon as possible, clear to it in the first [`ApplicationHandler::redraw`]. On the web, where the canvas is always visible, set its background color via CSS instead.
+    fn show_after_first_frame() -> bool {
+        false
+    }
+
+    /// Gets the [`winit::event_loop::ControlFlow`] the event loop starts with.
+    ///
+    /// It is set before any other events are processed, so e.g. continuously rendering applications can start with [`winit::event_loop::ControlFlow::Poll`] from the very first frame.
+    fn initial_control_flow() -> winit::event_loop::ControlFlow {
+        winit::event_loop::ControlFlow::default()
+    }
+
+    /// Whether the event loop should keep running after the window is closed, e.g. for macOS menu bar applications.
+    ///
+    /// If set, the window is destroyed after [`winit::event::WindowEvent::CloseRequested`] has been dispatched to [`ApplicationHandler::window_event`]. Like a suspension, wgpu is torn down and [`ApplicationHandler::suspended`] is called, so GPU resources must be dropped there. The window can be created again via [`Context::create_window`], after which [`ApplicationHandler::resumed`] is called as usual. Call [`winit::event_loop::ActiveEventLoop::exit`] to actually quit.
+    ///
+    /// If not set (the default), wginit does not handle [`winit::event::WindowEvent::CloseRequested`] and it is up to the application to exit.
+    fn keep_running_after_close() -> bool {
+        false
+    }
+
+    /// Whether window creation should be deferred until [`Context::create_window`] is called.
+    ///
+    /// This is useful for applications that need to do significant setup before showing a window, or that may not open a window at all. Until the window is created, [`Context::window`] and [`Context::wgpu`] are not available, and [`ApplicationHandler::resumed`] is not called.
+    fn defer_window_creation() -> bool {
+        false
+    }
+
+    /// Gets the [`wgpu::Dx12Compiler`] to compile shaders with on DX12.
+    ///
+    /// This is ignored on other backends. DXC supports newer shader features and compiles faster than FXC, but requires shipping `dxcompiler.dll` and `dxil.dll` alongside the application.
+    fn dx12_shader_compiler() -> wgpu::Dx12Compiler {
+        wgpu::Dx12Compiler::Fxc
+    }
+
+    /// Wraps the future that initializes wgpu on the web.
+    ///
+    /// On the web, initialization runs asynchronously on the browser's event loop. This allows e.g. inserting yields or reporting progress, so that an HTML loading indicator can be updated while WebGPU initializes.
+    #[cfg(target_arch = "wasm32")]
+    fn wrap_init_future(
+        init: std::pin::Pin<Box<dyn std::future::Future<Output = Result<Wgpu, WgpuInitError>>>>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = Result<Wgpu, WgpuInitError>>>> {
+        init
+    }
+
+    /// Gets the groups of backends to try, in order of preference.
+    ///
+    /// An instance is created for each group until one yields an adapter. By default, the primary backends (Vulkan, Metal, DX12 and WebGPU) are tried before falling back on GL. The backend that was chosen is available via [`wgpu::Adapter::get_info`] on [`Wgpu::adapter`].
+    fn backend_order() -> &'static [wgpu::Backends] {
+        &[wgpu::Backends::PRIMARY, wgpu::Backends::GL]
+    }
+
+    /// Whether WebGPU is required on the web, instead of falling back on WebGL if it is unavailable.
+    ///
+    /// If set and WebGPU is unavailable, initialization fails with [`WgpuInitError::WebGpuUnavailable`], e.g. to prompt the user to upgrade their browser rather than silently running on the slower and more limited WebGL path. At the time of writing, WebGPU is not available in all browsers, or only behind flags in some, so this may turn away a significant number of users. This is ignored on native platforms.
+    fn require_webgpu() -> bool {
+        false
     }
 
     /// Creates the [`wgpu::DeviceDescriptor`] to create a [`wgpu::Device`] with.
     ///
-    /// The defaults are compatible with WebGL.
+    /// The defaults are compatible with WebGL. [`wgpu::Features::PIPELINE_CACHE`] is requested if the adapter supports it, so that [`Wgpu::pipeline_cache`] can be created.
     fn device_descriptor(adapter: &wgpu::Adapter) -> wgpu::DeviceDescriptor {
         wgpu::DeviceDescriptor {
             required_limits: wgpu::Limits::downlevel_webgl2_defaults()
                 .using_resolution(adapter.limits()),
-            required_features: wgpu::Features::default(),
-            ..Default::default()
+            required_features: adapter.features() & wgpu::Features::PIPELINE_CACHE,
+            label: Some(Self::object_label_prefix()),
+            memory_hints: Self::memory_hints(),
         }
     }
 
+    /// Gets the [`wgpu::MemoryHints`] for the default [`ApplicationHandler::device_descriptor`].
+    ///
+    /// [`wgpu::MemoryHints::Performance`] lets the allocator reserve large memory blocks up front, which makes allocations fast but uses more memory. [`wgpu::MemoryHints::MemoryUsage`] allocates in smaller blocks, which suits memory-constrained platforms such as mobile or applications with many small resources. These hints only affect backends with their own allocator (Vulkan, DX12 and Metal) and are ignored on GL and the web.
+    fn memory_hints() -> wgpu::MemoryHints {
+        wgpu::MemoryHints::Performance
+    }
+
+    /// Gets the prefix for labels of wgpu objects created by wginit.
+    ///
+    /// Labels make these objects easy to spot in GPU debuggers such as RenderDoc.
+    fn object_label_prefix() -> &'static str {
+        "wginit"
+    }
+
+    /// Gets previously persisted pipeline cache data to initialize [`Wgpu::pipeline_cache`] with.
+    ///
+    /// <section class="warning">
+    ///
+    /// The data must have been previously returned by [`Wgpu::pipeline_cache_data`], ideally keyed by [`wgpu::util::pipeline_cache_key`]. wgpu validates the data's header and falls back to an empty cache if it is incompatible, but cannot fully validate untrusted data.
+    ///
+    /// </section>
+    fn pipeline_cache_data() -> Option<Vec<u8>> {
+        None
+    }
+
     /// Creates the [`wgpu::SurfaceConfiguration`] to configure a [`wgpu::Surface`] with.
     ///
     /// Note that the input size may be zero and it is up to the implementor to ensure a non-zero size on the surface configuration.
+    ///
+    /// wgpu does not expose surface transforms, so the surface is always configured in the window's current orientation. When a mobile device is rotated, the window is resized with swapped dimensions and the surface is reconfigured accordingly, with the backend handling any rotation (possibly at some compositing cost on Android).
     fn surface_configuration(
         surface: &wgpu::Surface,
         adapter: &wgpu::Adapter,
         size: winit::dpi::PhysicalSize<u32>,
     ) -> wgpu::SurfaceConfiguration {
-        surface
+        let mut config = surface
             .get_default_config(&adapter, size.width.max(1), size.height.max(1))
-            .unwrap()
+            .unwrap();
+        let capabilities = surface.get_capabilities(adapter);
+        let alpha_mode = Self::alpha_mode(&capabilities);
+        if alpha_mode == wgpu::CompositeAlphaMode::Auto
+            || capabilities.alpha_modes.contains(&alpha_mode)
+        {
+            config.alpha_mode = alpha_mode;
+        }
+        config.desired_maximum_frame_latency = Self::desired_maximum_frame_latency();
+        config.usage = Self::surface_usage();
+        if adapter
+            .get_downlevel_capabilities()
+            .flags
+            .contains(wgpu::DownlevelFlags::SURFACE_VIEW_FORMATS)
+        {
+            // Surface view formats may only differ from the surface format in sRGB-ness.
+            config.view_formats = Self::surface_view_formats(config.format)
+                .into_iter()
+                .filter(|view_format| {
+                    view_format.remove_srgb_suffix() == config.format.remove_srgb_suffix()
+                })
+                .collect();
+        }
+        config
+    }
+
+    /// Gets the [`wgpu::TextureUsages`] of the surface textures.
+    ///
+    /// This is applied by the default [`ApplicationHandler::surface_configuration`]. Adding e.g. [`wgpu::TextureUsages::STORAGE_BINDING`] or [`wgpu::TextureUsages::TEXTURE_BINDING`] allows post-processing the surface texture directly in a compute shader. Together with [`ApplicationHandler::surface_view_formats`], the usage is validated against the surface capabilities and format features when wgpu is initialized, failing with [`WgpuInitError::UnsupportedSurfaceUsage`] if unsupported.
+    ///
+    /// Vulkan, DX12 and Metal generally support sampling surface textures, and storage usage on non-sRGB formats (BGRA formats additionally require [`wgpu::Features::BGRA8UNORM_STORAGE`]). GL and WebGL only support rendering to the surface.
+    fn surface_usage() -> wgpu::TextureUsages {
+        wgpu::TextureUsages::RENDER_ATTACHMENT
+    }
+
+    /// Gets the maximum number of frames that may be queued for presentation ahead of the one being displayed, which controls the number of swapchain images.
+    ///
+    /// This is applied by the default [`ApplicationHandler::surface_configuration`] and is the only buffering knob wgpu exposes. Lower values reduce input latency at the cost of throughput: `1` is good for latency-sensitive applications such as games with fast input, while the default of `2` keeps the GPU busy for steadier frame rates. Values of `3` or more rarely help.
+    ///
+    /// How this maps to the swapchain image count is backend-dependent: Vulkan and DX12 use one more image than this value, Metal clamps it to 2 or 3 images, and GL ignores it.
+    fn desired_maximum_frame_latency() -> u32 {
+        2
+    }
+
+    /// Gets additional formats that views of the surface texture may use.
+    ///
+    /// This is typically used to render through an sRGB view of a non-sRGB surface. Formats that differ from the surface format in more than sRGB-ness are not supported and will be ignored, as will all view formats on backends that do not support them. The first view format is used by [`Wgpu::surface_view`].
+    fn surface_view_formats(format: wgpu::TextureFormat) -> Vec<wgpu::TextureFormat> {
+        let _ = format;
+        vec![]
+    }
+
+    /// Adjusts the complete [`wgpu::SurfaceConfiguration`] right before it is applied.
+    ///
+    /// This is an escape hatch that runs last, after [`ApplicationHandler::surface_configuration`] (and therefore after all the narrower hooks it uses, such as [`ApplicationHandler::alpha_mode`]). It is called both when wgpu is initialized and whenever the surface is reconfigured on resize.
+    fn adjust_surface_config(config: &mut wgpu::SurfaceConfiguration) {
+        let _ = config;
+    }
+
+    /// Chooses the [`wgpu::CompositeAlphaMode`] for the default [`ApplicationHandler::surface_configuration`].
+    ///
+    /// If the returned alpha mode is not in [`wgpu::SurfaceCapabilities::alpha_modes`], the first supported alpha mode is used instead. The chosen alpha mode is available via [`Wgpu::surface_config`].
+    fn alpha_mode(capabilities: &wgpu::SurfaceCapabilities) -> wgpu::CompositeAlphaMode {
+        capabilities
+            .alpha_modes
+            .first()
+            .copied()
+            .unwrap_or(wgpu::CompositeAlphaMode::Auto)
     }
 
     /// Creates the [`wgpu::RequestAdapterOptions`] to request a [`wgpu::Adapter`] with.
@@ -385,11 +2429,20 @@ where
         }
     }
 
+    /// Whether a fallback (software) adapter may be requested if no other adapter can be found.
+    ///
+    /// This is tried as a last resort after the default backends and GL, e.g. for headless CI without a GPU.
+    fn allow_fallback_adapter() -> bool {
+        true
+    }
+
     /// Creates a new instance of this application.
     fn new(user_event_sender: UserEventSender<Self::UserEvent>) -> Self;
 
     /// Handles application resumption.
     ///
+    /// This is called once both the window and wgpu are ready. If the window initially has a zero size, wgpu initialization (and therefore this) is deferred until the window has a non-zero size, so the surface is never configured with a placeholder size.
+    ///
     /// - [`Context::window`]\: Available.
     /// - [`Context::wgpu`]\: Available.
     ///
@@ -398,28 +2451,62 @@ where
         let _ = ctxt;
     }
 
-    /// Handles application memory warnings.
+    /// Handles wgpu failing to initialize.
+    ///
+    /// By default, this panics.
     ///
     /// - [`Context::window`]\: Available.
-    /// - [`Context::wgpu`]\: Available.
+    /// - [`Context::wgpu`]\: Not available.
+    fn wgpu_init_failed(&mut self, ctxt: &Context, error: WgpuInitError) {
+        let _ = ctxt;
+        panic!("{error}");
+    }
+
+    /// Handles a wgpu error, such as a validation error.
+    ///
+    /// Errors are collected as they happen, starting as soon as the device has been created (so errors during initialization, e.g. when configuring the surface, are included), and delivered before [`ApplicationHandler::about_to_wait`]. By default, this panics, same as wgpu does without an error handler. Override it to e.g. log errors or degrade gracefully instead.
+    ///
+    /// If the error was captured during a redraw via [`ApplicationHandler::capture_frame_errors`], `frame_index` is the index of that frame, counting every call to [`ApplicationHandler::redraw`] from zero. It is [`None`] for uncaptured errors.
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn wgpu_error(&mut self, ctxt: &Context, error: wgpu::Error, frame_index: Option<u64>) {
+        let _ = ctxt;
+        match frame_index {
+            Some(frame_index) => panic!("wgpu error in frame {frame_index}: {error}"),
+            None => panic!("wgpu error: {error}"),
+        }
+    }
+
+    /// Handles application memory warnings.
+    ///
+    /// The warning is always delivered, even if wgpu is not available. If wgpu is available, [`ApplicationHandler::trim_memory`] will be called right after.
+    ///
+    /// - [`Context::window`]\: May or may not be available.
+    /// - [`Context::wgpu`]\: May or may not be available.
     ///
     /// See [`winit::application::ApplicationHandler::memory_warning`] for more details.
     fn memory_warning(&mut self, ctxt: &Context) {
         let _ = ctxt;
     }
 
+    /// Handles freeing GPU memory in response to a memory warning.
+    ///
+    /// It will run right after [`ApplicationHandler::memory_warning`] *if* wgpu is initialized. This is a good place to drop GPU caches.
+    fn trim_memory(&mut self, wgpu: &Wgpu) {
+        let _ = wgpu;
+    }
+
     /// Handles when the application receives new events ready to be processed.
     ///
-    /// - [`Context::window`]\: Not available.
-    /// - [`Context::wgpu`]\: Not available.
+    /// With [`ApplicationHandler::defer_window_creation`], this is a good place to call [`Context::create_window`] once setup has finished, e.g. on [`winit::event::StartCause::Init`].
+    ///
+    /// - [`Context::window`]\: May or may not be available.
+    /// - [`Context::wgpu`]\: May or may not be available.
     ///
     /// See [`winit::application::ApplicationHandler::new_events`] for more details.
-    fn new_events(
-        &mut self,
-        event_loop: &winit::event_loop::ActiveEventLoop,
-        start_cause: winit::event::StartCause,
-    ) {
-        let _ = (event_loop, start_cause);
+    fn new_events(&mut self, ctxt: &Context, start_cause: winit::event::StartCause) {
+        let _ = (ctxt, start_cause);
     }
 
     /// Handles when the application is about to block and wait for new events.
@@ -432,6 +2519,28 @@ where
         let _ = ctxt;
     }
 
+    /// Chooses the [`winit::event_loop::ControlFlow`] for the next wait, right after [`ApplicationHandler::about_to_wait`].
+    ///
+    /// This allows e.g. polling continuously only while something is animating, and waiting for events otherwise. By default, the current control flow is kept, so control flow set elsewhere (such as [`ApplicationHandler::initial_control_flow`]) is unaffected.
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn next_control_flow(&mut self, ctxt: &Context) -> winit::event_loop::ControlFlow {
+        ctxt.event_loop.control_flow()
+    }
+
+    /// Handles the device or adapter being lost, e.g. when an external GPU is unplugged or the driver is reset.
+    ///
+    /// Rather than failing, wginit tears down the wgpu state and initializes it again, requesting an adapter via [`ApplicationHandler::request_adapter_options`] so a replacement can be picked. As with a suspension, all GPU resources must be dropped here and recreated in [`ApplicationHandler::resumed`], which will be called again once wgpu is ready. If no replacement can be found, [`ApplicationHandler::wgpu_init_failed`] is called instead.
+    ///
+    /// Loss is detected via the device lost callback, so it is only noticed on backends that report it.
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: Not available.
+    fn adapter_lost(&mut self, ctxt: &Context) {
+        let _ = ctxt;
+    }
+
     /// Handles application suspension.
     ///
     /// - [`Context::window`]\: Available.
@@ -452,6 +2561,17 @@ where
         let _ = ctxt;
     }
 
+    /// Handles the event loop having stopped, consuming the application.
+    ///
+    /// Unlike [`ApplicationHandler::exiting`], which runs while the event loop is still dispatching events, this runs after the event loop has returned, so no more events can arrive. This makes it a safe place to e.g. join threads that send user events or flush files.
+    ///
+    /// This is not called on the web, where the event loop never returns.
+    fn finished(self)
+    where
+        Self: Sized,
+    {
+    }
+
     /// Handles a window event.
     ///
     /// wginit will handle [`winit::event::WindowEvent::Resized`] to update the size of the wgpu surface. You must handle all other events yourself.
@@ -464,6 +2584,56 @@ where
         let _ = (ctxt, event);
     }
 
+    /// Handles a trackpad or touch gesture, e.g. for zooming, rotating and panning in map or image viewers.
+    ///
+    /// This is called before [`ApplicationHandler::window_event`] for the corresponding [`winit::event::WindowEvent::PinchGesture`], [`winit::event::WindowEvent::RotationGesture`] or [`winit::event::WindowEvent::PanGesture`]. Gestures are currently only emitted on macOS and iOS.
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn gesture(&mut self, ctxt: &Context, gesture: GestureEvent) {
+        let _ = (ctxt, gesture);
+    }
+
+    /// Handles the window's scale factor changing, e.g. when it is moved to a monitor with a different DPI.
+    ///
+    /// This is called before [`ApplicationHandler::window_event`] for the corresponding [`winit::event::WindowEvent::ScaleFactorChanged`]. By default, winit resizes the window to keep its logical size; `inner_size_writer` can be used to request a different new inner size instead, e.g. to keep the physical size. If the window's physical size changes as a result, a [`winit::event::WindowEvent::Resized`] follows, which reconfigures the surface.
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn scale_factor_changed(
+        &mut self,
+        ctxt: &Context,
+        scale_factor: f64,
+        inner_size_writer: &mut winit::event::InnerSizeWriter,
+    ) {
+        let _ = (ctxt, scale_factor, inner_size_writer);
+    }
+
+    /// Handles the window moving to a different monitor.
+    ///
+    /// This can be used to e.g. re-tune quality settings for a monitor with a different refresh rate. It is called before [`ApplicationHandler::window_event`] for the corresponding [`winit::event::WindowEvent::Moved`].
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn monitor_changed(&mut self, ctxt: &Context, monitor: Option<winit::monitor::MonitorHandle>) {
+        let _ = (ctxt, monitor);
+    }
+
+    /// Intercepts a user event before it is dispatched to [`ApplicationHandler::user_event`].
+    ///
+    /// Returning [`None`] consumes the event, while returning [`Some`] forwards the (possibly modified) event. This allows e.g. layered applications to preprocess user events centrally. By default, all events are forwarded unchanged.
+    ///
+    /// - [`Context::window`]\: May or may not be available.
+    /// - [`Context::wgpu`]\: May or may not be available.
+    fn pre_user_event(
+        &mut self,
+        ctxt: &Context,
+        event: Self::UserEvent,
+    ) -> Option<Self::UserEvent> {
+        let _ = ctxt;
+        Some(event)
+    }
+
     /// Handles a user event.
     ///
     /// User events can be sent using [`UserEventSender`].
@@ -489,11 +2659,89 @@ where
         let _ = (ctxt, device_id, event);
     }
 
+    /// Gets how many times the surface is reconfigured when acquiring the first frame after initialization times out.
+    ///
+    /// Some drivers (notably certain Vulkan drivers) report [`wgpu::SurfaceError::Timeout`] for a short while after the surface is configured. While retries remain, returning that error from the first [`ApplicationHandler::redraw`] reconfigures the surface before retrying. Once exhausted, the frame is skipped and a redraw is requested as usual.
+    fn first_frame_retries() -> u32 {
+        3
+    }
+
+    /// Whether wginit should automatically reconfigure the surface after it is initially configured.
+    ///
+    /// If this is disabled, the surface is not reconfigured on resize, on [`Context::set_present_mode`], or when [`ApplicationHandler::redraw`] reports a lost or outdated surface. Instead, the application is responsible for calling [`wgpu::Surface::configure`] itself, e.g. from [`ApplicationHandler::surface_resized`]. A frame reporting a lost or outdated surface is skipped without requesting another redraw, so the application should reconfigure the surface and request a redraw itself. Timeouts on the first frame are not retried by reconfiguring either. Note that [`Wgpu::surface_config`] will then only reflect the initial configuration.
+    fn manage_surface() -> bool {
+        true
+    }
+
+    /// Handles the surface size changing due to the window being resized.
+    ///
+    /// If [`ApplicationHandler::manage_surface`] is enabled, the surface has already been reconfigured at this point. This is called before [`ApplicationHandler::window_event`] for the corresponding [`winit::event::WindowEvent::Resized`].
+    ///
+    /// - [`Context::window`]\: Available.
+    /// - [`Context::wgpu`]\: Available.
+    fn surface_resized(&mut self, ctxt: &Context, size: winit::dpi::PhysicalSize<u32>) {
+        let _ = (ctxt, size);
+    }
+
+    /// Whether a redraw should be requested when the window is resized.
+    ///
+    /// The surface is always reconfigured on resize regardless. If this is disabled, the application is responsible for requesting a redraw itself, e.g. for retained-mode UIs that render on their own schedule.
+    fn redraw_on_resize() -> bool {
+        true
+    }
+
+    /// Whether [`ApplicationHandler::redraw`] should be called synchronously when the window is resized, instead of just requesting a redraw.
+    ///
+    /// On some platforms, not presenting a correctly-sized frame within the resize event causes black or stale flickering during live resizes. Enabling this eliminates the flicker, but can make resizing feel heavier as every resize event renders a frame.
+    fn render_during_resize() -> bool {
+        false
+    }
+
+    /// Prepares GPU work ahead of the first redraw, e.g. creating pipelines so the first visible frame is not stalled by shader compilation.
+    ///
+    /// It will run every time wgpu is initialized, right after [`ApplicationHandler::resumed`] and before the first [`ApplicationHandler::redraw`]. Combined with [`ApplicationHandler::show_after_first_frame`], nothing will be shown until this has finished.
+    fn prewarm(&mut self, wgpu: &Wgpu) {
+        let _ = wgpu;
+    }
+
     /// Handles a redraw request.
     ///
     /// It will run whenever [`winit::event::WindowEvent::RedrawRequested`] is emitted *and* wgpu is initialized.
-    fn redraw(&mut self, window: &winit::window::Window, wgpu: &Wgpu) {
+    ///
+    /// Errors from [`wgpu::Surface::get_current_texture`] can be returned to let wginit recover from them: on [`wgpu::SurfaceError::Lost`] or [`wgpu::SurfaceError::Outdated`] the surface is reconfigured and another redraw is requested, on [`wgpu::SurfaceError::Timeout`] another redraw is requested (see also [`ApplicationHandler::first_frame_retries`]), and on [`wgpu::SurfaceError::OutOfMemory`] wginit panics.
+    fn redraw(
+        &mut self,
+        window: &winit::window::Window,
+        wgpu: &Wgpu,
+    ) -> Result<(), wgpu::SurfaceError> {
         let _ = (window, wgpu);
+        Ok(())
+    }
+
+    /// Whether each [`ApplicationHandler::redraw`] should be wrapped in a validation error scope.
+    ///
+    /// If set, validation errors caused during a redraw are delivered via [`ApplicationHandler::wgpu_error`] before the next [`ApplicationHandler::about_to_wait`], along with the index of the frame. This makes it easier to pinpoint the frame that caused them. This is disabled by default as error scopes have some overhead.
+    fn capture_frame_errors() -> bool {
+        false
+    }
+
+    /// Gets the wall-clock time budget for a single [`ApplicationHandler::redraw`], if any.
+    ///
+    /// If set, [`ApplicationHandler::frame_overrun`] will be called whenever a redraw takes longer than the budget.
+    fn frame_budget() -> Option<std::time::Duration> {
+        None
+    }
+
+    /// Handles a redraw that exceeded [`ApplicationHandler::frame_budget`].
+    ///
+    /// The time is measured from the start of [`ApplicationHandler::redraw`] until it returns, which usually includes presenting the frame. `frame_index` counts every call to [`ApplicationHandler::redraw`] from zero, the same as in [`ApplicationHandler::wgpu_error`], so overruns can be correlated with errors captured in the same frame.
+    fn frame_overrun(
+        &mut self,
+        actual: std::time::Duration,
+        budget: std::time::Duration,
+        frame_index: u64,
+    ) {
+        let _ = (actual, budget, frame_index);
     }
 }
 
@@ -504,11 +2752,69 @@ pub fn run<A>() -> Result<(), winit::error::EventLoopError>
 where
     A: ApplicationHandler,
 {
-    let event_loop = winit::event_loop::EventLoop::with_user_event().build()?;
-    let mut app = WinitApplicationHandler::new(
-        A::new(UserEventSender(event_loop.create_proxy())),
-        &event_loop,
-    );
+    let mut builder = winit::event_loop::EventLoop::with_user_event();
+    A::configure_event_loop(&mut builder);
+    let event_loop = builder.build()?;
+    let user_event_sender = UserEventSender::new(&event_loop);
+    run_event_loop::<A>(event_loop, user_event_sender, None)
+}
+
+/// Runs the application on the given event loop.
+///
+/// If a spawner is given, wgpu initialization is handed to it instead of being run on the event loop thread.
+fn run_event_loop<A>(
+    event_loop: winit::event_loop::EventLoop<UserEvent<A::UserEvent>>,
+    user_event_sender: UserEventSender<A::UserEvent>,
+    spawner: Option<Spawner>,
+) -> Result<(), winit::error::EventLoopError>
+where
+    A: ApplicationHandler,
+{
+    let closed = user_event_sender.closed.clone();
+    let mut app =
+        WinitApplicationHandler::new(A::new(user_event_sender), &event_loop, closed, spawner);
     event_loop.run_app(&mut app)?;
+    app.shutdown().finished();
     Ok(())
 }
+
+/// Runs the application, initializing wgpu on the given tokio runtime.
+///
+/// This is like [`run`], but instead of blocking the event loop thread on wgpu initialization, the initialization future is spawned onto the runtime and its result delivered back to the event loop. This avoids conflicts with blocking inside an existing tokio runtime.
+///
+/// The wgpu instance and surface are still created on the event loop thread, as some platforms (e.g. macOS and iOS) require the surface to be created there. Only requesting the adapter and device and configuring the surface run on the runtime.
+///
+/// As the initialization runs on the runtime's worker threads, [`ApplicationHandler::UserEvent`] must be [`Send`]. The event loop itself still runs on the calling thread, which must be the main thread on most platforms.
+///
+/// As the event loop blocks the calling thread, a [current-thread](tokio::runtime::RuntimeFlavor::CurrentThread) runtime driven by it would never get to run the initialization. For such runtimes, initialization blocks the event loop thread as in [`run`] instead, so use a multi-thread runtime to benefit from this.
+#[cfg(all(feature = "tokio", not(target_arch = "wasm32")))]
+pub fn run_with_runtime<A>(
+    handle: tokio::runtime::Handle,
+) -> Result<(), winit::error::EventLoopError>
+where
+    A: ApplicationHandler + 'static,
+    A::UserEvent: Send,
+{
+    let mut builder = winit::event_loop::EventLoop::with_user_event();
+    A::configure_event_loop(&mut builder);
+    let event_loop = builder.build()?;
+    let user_event_sender = UserEventSender::new(&event_loop);
+    if handle.runtime_flavor() == tokio::runtime::RuntimeFlavor::CurrentThread {
+        return run_event_loop::<A>(event_loop, user_event_sender, None);
+    }
+    let event_loop_proxy = event_loop.create_proxy();
+    run_event_loop::<A>(
+        event_loop,
+        user_event_sender,
+        Some(Box::new(move |pending| {
+            let event_loop_proxy = event_loop_proxy.clone();
+            let suspend_count = pending.suspend_count;
+            handle.spawn(async move {
+                let _ = event_loop_proxy.send_event(UserEvent::wgpu_ready(
+                    suspend_count,
+                    Wgpu::from_surface::<A>(pending).await,
+                ));
+            });
+        })),
+    )
+}
@@ -0,0 +1,284 @@
+//! The lifecycle of the window and wgpu state, kept separate from winit so its transitions can be tested without a window.
+
+use winit::dpi::PhysicalSize;
+
+/// The lifecycle state of the application.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub(crate) enum LifecycleState {
+    /// The application has not been resumed yet.
+    Uninitialized,
+    /// wgpu is initialized and no suspension has happened since.
+    Running,
+    /// The application has been suspended and wgpu has not been reinitialized yet.
+    Suspended,
+}
+
+/// Tracks the [`LifecycleState`] along with everything needed to decide when wgpu should be initialized.
+#[derive(Debug)]
+pub(crate) struct Lifecycle {
+    state: LifecycleState,
+    awaiting_size: bool,
+    suspend_count: u64,
+}
+
+impl Default for Lifecycle {
+    fn default() -> Self {
+        Self {
+            state: LifecycleState::Uninitialized,
+            awaiting_size: false,
+            suspend_count: 0,
+        }
+    }
+}
+
+impl Lifecycle {
+    /// Gets the current lifecycle state.
+    pub(crate) fn state(&self) -> LifecycleState {
+        self.state
+    }
+
+    /// Returns if wginit may drive a redraw, i.e. wgpu is initialized and the application has not been suspended since.
+    pub(crate) fn can_redraw(&self) -> bool {
+        self.state == LifecycleState::Running
+    }
+
+    /// Returns if wgpu has never been torn down, i.e. this is the initial resume.
+    pub(crate) fn is_cold_start(&self) -> bool {
+        self.suspend_count == 0
+    }
+
+    /// Gets how many times the wgpu state has been torn down.
+    ///
+    /// wgpu state is tagged with this when initialization starts, so that a stale initialization can be detected in [`Lifecycle::wgpu_ready`].
+    pub(crate) fn suspend_count(&self) -> u64 {
+        self.suspend_count
+    }
+
+    /// Handles the window being available with the given size, returning if wgpu should be initialized now.
+    ///
+    /// Some platforms report a zero size right after the window is created. Rather than configuring a bogus 1x1 surface, initialization waits for the first real size via [`Lifecycle::resized`].
+    pub(crate) fn window_ready(&mut self, size: PhysicalSize<u32>) -> bool {
+        self.awaiting_size = size.width == 0 || size.height == 0;
+        !self.awaiting_size
+    }
+
+    /// Handles the window being resized, returning if wgpu should be initialized now because it was waiting for a real size.
+    pub(crate) fn resized(&mut self, size: PhysicalSize<u32>) -> bool {
+        if !self.awaiting_size || size.width == 0 || size.height == 0 {
+            return false;
+        }
+        self.awaiting_size = false;
+        true
+    }
+
+    /// Handles wgpu having been initialized for the given suspend count, returning if it should be used.
+    ///
+    /// If wgpu was torn down again while it was initializing (e.g. on the web, where initialization is asynchronous), the initialized state is stale and must be dropped.
+    pub(crate) fn wgpu_ready(&mut self, suspend_count: u64) -> bool {
+        if suspend_count != self.suspend_count {
+            return false;
+        }
+        self.state = LifecycleState::Running;
+        true
+    }
+
+    /// Handles wgpu initialization having failed for the given suspend count, returning if the failure should be reported.
+    ///
+    /// As with [`Lifecycle::wgpu_ready`], a failure of an initialization that started before wgpu was torn down again is stale.
+    pub(crate) fn wgpu_failed(&self, suspend_count: u64) -> bool {
+        suspend_count == self.suspend_count
+    }
+
+    /// Handles the application being suspended.
+    pub(crate) fn suspend(&mut self) {
+        self.tear_down();
+    }
+
+    /// Handles the window being closed without exiting.
+    pub(crate) fn close(&mut self) {
+        self.tear_down();
+    }
+
+    /// Handles a request to reinitialize wgpu, returning if wgpu should be torn down and initialized again.
+    ///
+    /// This only applies while running, as otherwise wgpu is either not initialized or about to be.
+    pub(crate) fn reinitialize(&mut self) -> bool {
+        if self.state != LifecycleState::Running {
+            return false;
+        }
+        self.tear_down();
+        true
+    }
+
+    /// Handles the device being lost, returning if wgpu should be torn down and initialized again.
+    ///
+    /// This only applies while running, so a loss is handled only once.
+    pub(crate) fn device_lost(&mut self) -> bool {
+        if self.state != LifecycleState::Running {
+            return false;
+        }
+        self.tear_down();
+        true
+    }
+
+    /// Marks the wgpu state as torn down, which also invalidates any initialization in progress.
+    fn tear_down(&mut self) {
+        self.state = LifecycleState::Suspended;
+        self.awaiting_size = false;
+        self.suspend_count += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Creates a lifecycle that has been resumed with a real size and has wgpu initialized.
+    fn running() -> Lifecycle {
+        let mut lifecycle = Lifecycle::default();
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+        lifecycle
+    }
+
+    #[test]
+    fn zero_then_real_size() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(!lifecycle.window_ready(PhysicalSize::new(0, 0)));
+        assert!(!lifecycle.resized(PhysicalSize::new(0, 0)));
+        assert!(!lifecycle.resized(PhysicalSize::new(800, 0)));
+        assert!(lifecycle.resized(PhysicalSize::new(800, 600)));
+        assert_eq!(lifecycle.state(), LifecycleState::Uninitialized);
+
+        // Only the first real size starts initialization.
+        assert!(!lifecycle.resized(PhysicalSize::new(1024, 768)));
+
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+        assert_eq!(lifecycle.state(), LifecycleState::Running);
+    }
+
+    #[test]
+    fn real_initial_size() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(!lifecycle.resized(PhysicalSize::new(1024, 768)));
+    }
+
+    #[test]
+    fn no_redraw_while_suspended() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(!lifecycle.can_redraw());
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(!lifecycle.can_redraw());
+        let stale_suspend_count = lifecycle.suspend_count();
+        assert!(lifecycle.wgpu_ready(stale_suspend_count));
+        assert!(lifecycle.can_redraw());
+
+        lifecycle.suspend();
+        assert!(!lifecycle.can_redraw());
+
+        // An initialization that started before the suspension must not enable redraws again.
+        assert!(!lifecycle.wgpu_ready(stale_suspend_count));
+        assert!(!lifecycle.can_redraw());
+
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(!lifecycle.can_redraw());
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+        assert!(lifecycle.can_redraw());
+    }
+
+    #[test]
+    fn suspend_while_awaiting_size() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(!lifecycle.window_ready(PhysicalSize::new(0, 0)));
+        lifecycle.suspend();
+        assert!(!lifecycle.resized(PhysicalSize::new(800, 600)));
+
+        // Resuming checks the size again.
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+    }
+
+    #[test]
+    fn android_suspend_resume() {
+        let mut lifecycle = running();
+        assert!(lifecycle.is_cold_start());
+
+        // Every resume initializes wgpu from scratch, as the native window changes.
+        lifecycle.suspend();
+        assert!(!lifecycle.is_cold_start());
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        let suspend_count = lifecycle.suspend_count();
+
+        // Suspending again before initialization has finished makes it stale.
+        lifecycle.suspend();
+        assert!(!lifecycle.wgpu_ready(suspend_count));
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+        assert!(!lifecycle.is_cold_start());
+    }
+
+    #[test]
+    fn stale_failure() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        let stale_suspend_count = lifecycle.suspend_count();
+        lifecycle.suspend();
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+
+        // A failure of the superseded initialization must not be reported once the newer one has succeeded.
+        assert!(!lifecycle.wgpu_failed(stale_suspend_count));
+        assert!(lifecycle.wgpu_failed(lifecycle.suspend_count()));
+        assert!(lifecycle.can_redraw());
+    }
+
+    #[test]
+    fn reinitialize() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(!lifecycle.reinitialize());
+        assert!(lifecycle.is_cold_start());
+
+        let mut lifecycle = running();
+        let suspend_count = lifecycle.suspend_count();
+        assert!(lifecycle.reinitialize());
+        assert!(!lifecycle.is_cold_start());
+        assert!(!lifecycle.can_redraw());
+        assert!(!lifecycle.wgpu_ready(suspend_count));
+
+        // Requests are ignored until wgpu has been initialized again.
+        assert!(!lifecycle.reinitialize());
+        assert!(lifecycle.wgpu_ready(suspend_count + 1));
+    }
+
+    #[test]
+    fn device_lost() {
+        let mut lifecycle = Lifecycle::default();
+        assert!(!lifecycle.device_lost());
+        assert!(lifecycle.is_cold_start());
+
+        let mut lifecycle = running();
+        let suspend_count = lifecycle.suspend_count();
+        assert!(lifecycle.device_lost());
+        assert!(!lifecycle.is_cold_start());
+        assert!(!lifecycle.can_redraw());
+        assert!(!lifecycle.wgpu_ready(suspend_count));
+
+        // The loss is only handled once.
+        assert!(!lifecycle.device_lost());
+        assert!(lifecycle.wgpu_ready(suspend_count + 1));
+    }
+
+    #[test]
+    fn close() {
+        let mut lifecycle = running();
+        let suspend_count = lifecycle.suspend_count();
+        lifecycle.close();
+        assert!(!lifecycle.is_cold_start());
+        assert!(!lifecycle.can_redraw());
+        assert!(!lifecycle.wgpu_ready(suspend_count));
+
+        // Recreating the window starts over.
+        assert!(lifecycle.window_ready(PhysicalSize::new(800, 600)));
+        assert!(lifecycle.wgpu_ready(lifecycle.suspend_count()));
+    }
+}
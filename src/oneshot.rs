@@ -0,0 +1,52 @@
+//! A minimal oneshot channel for awaiting wgpu callbacks.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll, Waker};
+
+struct Shared<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+}
+
+/// The sending half of a oneshot channel.
+pub(crate) struct Sender<T>(Arc<Mutex<Shared<T>>>);
+
+/// The receiving half of a oneshot channel, which resolves to the sent value.
+pub(crate) struct Receiver<T>(Arc<Mutex<Shared<T>>>);
+
+/// Creates a new oneshot channel.
+pub(crate) fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(Shared {
+        value: None,
+        waker: None,
+    }));
+    (Sender(shared.clone()), Receiver(shared))
+}
+
+impl<T> Sender<T> {
+    /// Sends the value, waking the receiver if it is waiting.
+    pub(crate) fn send(self, value: T) {
+        let mut shared = self.0.lock().unwrap();
+        shared.value = Some(value);
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<T> {
+        let mut shared = self.0.lock().unwrap();
+        match shared.value.take() {
+            Some(value) => Poll::Ready(value),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
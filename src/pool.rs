@@ -0,0 +1,37 @@
+//! A small, lazily started thread pool for running background futures.
+
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex, OnceLock};
+
+type Job = Box<dyn FnOnce() + Send>;
+
+static POOL: OnceLock<mpsc::Sender<Job>> = OnceLock::new();
+
+/// Runs a future to completion on the thread pool.
+pub(crate) fn spawn(fut: impl std::future::Future<Output = ()> + Send + 'static) {
+    let sender = POOL.get_or_init(|| {
+        let (tx, rx) = mpsc::channel::<Job>();
+        let rx = Arc::new(Mutex::new(rx));
+        let num_threads = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1)
+            .min(4);
+        for i in 0..num_threads {
+            let rx = rx.clone();
+            std::thread::Builder::new()
+                .name(format!("wginit-worker-{i}"))
+                .spawn(move || loop {
+                    // Only hold the lock while waiting for a job, so other workers can pick up jobs while this one runs.
+                    let job = rx.lock().unwrap().recv();
+                    let Ok(job) = job else {
+                        return;
+                    };
+                    // A panicking future must not take the worker down with it, or the pool would eventually run out of workers.
+                    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(job));
+                })
+                .expect("failed to spawn worker thread");
+        }
+        tx
+    });
+    let _ = sender.send(Box::new(move || pollster::block_on(fut)));
+}
@@ -0,0 +1,92 @@
+//! Running the application on a dedicated thread, on platforms where winit supports it.
+
+use crate::{run_event_loop, ApplicationHandler, UserEvent, UserEventSender};
+
+/// A handle to an application running on its own thread.
+///
+/// See [`run_on_thread`].
+pub struct AppHandle<C>
+where
+    C: 'static,
+{
+    user_event_sender: UserEventSender<C>,
+    thread: std::thread::JoinHandle<Result<(), winit::error::EventLoopError>>,
+}
+
+impl<C> AppHandle<C>
+where
+    C: 'static,
+{
+    /// Sends a user event to the application.
+    pub fn send_event(&self, event: C) -> Result<(), winit::event_loop::EventLoopClosed<C>> {
+        self.user_event_sender.send_event(event)
+    }
+
+    /// Requests the application to exit.
+    ///
+    /// This does nothing if the application has already exited.
+    pub fn request_exit(&self) {
+        let _ = self.user_event_sender.proxy.send_event(UserEvent::Exit);
+    }
+
+    /// Waits for the application to exit.
+    ///
+    /// If the application panicked, the panic is propagated.
+    pub fn join(self) -> Result<(), winit::error::EventLoopError> {
+        match self.thread.join() {
+            Ok(result) => result,
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+}
+
+/// Runs the application on a dedicated thread.
+///
+/// This returns immediately with an [`AppHandle`] that can be used to control the application, e.g. from integration tests or scripting tools.
+///
+/// <section class="warning">
+///
+/// winit only supports running the event loop off the main thread on some platforms, so this is only available on Windows, Linux and the BSDs. In particular, it is not available on macOS, where the event loop must run on the main thread.
+///
+/// </section>
+pub fn run_on_thread<A>() -> Result<AppHandle<A::UserEvent>, winit::error::EventLoopError>
+where
+    A: ApplicationHandler + 'static,
+    A::UserEvent: Send,
+{
+    let (tx, rx) = std::sync::mpsc::channel();
+    let thread = std::thread::spawn(move || {
+        let mut builder = winit::event_loop::EventLoop::with_user_event();
+        #[cfg(target_os = "windows")]
+        {
+            use winit::platform::windows::EventLoopBuilderExtWindows as _;
+            builder.with_any_thread(true);
+        }
+        #[cfg(not(target_os = "windows"))]
+        {
+            winit::platform::x11::EventLoopBuilderExtX11::with_any_thread(&mut builder, true);
+            winit::platform::wayland::EventLoopBuilderExtWayland::with_any_thread(
+                &mut builder,
+                true,
+            );
+        }
+        A::configure_event_loop(&mut builder);
+        let event_loop = builder.build()?;
+        let user_event_sender = UserEventSender::new(&event_loop);
+        let _ = tx.send(user_event_sender.clone());
+        run_event_loop::<A>(event_loop, user_event_sender, None)
+    });
+
+    let Ok(user_event_sender) = rx.recv() else {
+        // The thread exited before the event loop was built, so it must have failed.
+        return Err(match thread.join() {
+            Ok(result) => result.expect_err("event loop thread exited without running"),
+            Err(payload) => std::panic::resume_unwind(payload),
+        });
+    };
+
+    Ok(AppHandle {
+        user_event_sender,
+        thread,
+    })
+}
@@ -0,0 +1,62 @@
+//! Tests that user events sent from the event loop thread itself wake up the event loop.
+//!
+//! This needs a display server, so it is ignored by default and only run manually via `cargo test -- --ignored`.
+#![cfg(any_thread_event_loop)]
+
+/// Notified once the last step has been delivered.
+static DONE: std::sync::Mutex<Option<std::sync::mpsc::Sender<()>>> = std::sync::Mutex::new(None);
+
+/// How many times the application schedules more work for itself.
+const STEPS: u32 = 3;
+
+struct Step(u32);
+
+struct App {
+    user_event_sender: wginit::UserEventSender<Step>,
+}
+
+impl wginit::ApplicationHandler for App {
+    type UserEvent = Step;
+
+    fn new(user_event_sender: wginit::UserEventSender<Self::UserEvent>) -> Self {
+        Self { user_event_sender }
+    }
+
+    fn defer_window_creation() -> bool {
+        // Nothing is ever drawn, so the only thing that can wake up the event loop is the user event.
+        true
+    }
+
+    fn new_events(&mut self, _ctxt: &wginit::Context, start_cause: winit::event::StartCause) {
+        if matches!(start_cause, winit::event::StartCause::Init) {
+            assert!(self.user_event_sender.send_event(Step(1)).is_ok());
+        }
+    }
+
+    fn user_event(&mut self, _ctxt: &wginit::Context, event: Self::UserEvent) {
+        let Step(step) = event;
+        if step < STEPS {
+            assert!(self.user_event_sender.send_event(Step(step + 1)).is_ok());
+            return;
+        }
+        if let Some(done) = DONE.lock().unwrap().take() {
+            let _ = done.send(());
+        }
+    }
+}
+
+#[test]
+#[ignore = "requires a display"]
+fn wakes_up_under_wait() {
+    let (tx, rx) = std::sync::mpsc::channel();
+    *DONE.lock().unwrap() = Some(tx);
+
+    let app = wginit::run_on_thread::<App>().unwrap();
+    let result = rx.recv_timeout(std::time::Duration::from_secs(5));
+    app.request_exit();
+    app.join().unwrap();
+    assert!(
+        result.is_ok(),
+        "self-scheduled events were not delivered under ControlFlow::Wait"
+    );
+}
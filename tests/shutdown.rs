@@ -0,0 +1,37 @@
+//! Tests that exiting right after wgpu has been initialized shuts down cleanly.
+//!
+//! This needs a display server (and ideally a GPU), so it is ignored by default and only run manually via `cargo test -- --ignored`.
+#![cfg(any_thread_event_loop)]
+
+/// Set once the application has been handed back after the event loop exited.
+static FINISHED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+struct App;
+
+impl wginit::ApplicationHandler for App {
+    type UserEvent = ();
+
+    fn new(_user_event_sender: wginit::UserEventSender<Self::UserEvent>) -> Self {
+        Self
+    }
+
+    fn resumed(&mut self, ctxt: &wginit::Context) {
+        ctxt.event_loop.exit();
+    }
+
+    fn wgpu_init_failed(&mut self, ctxt: &wginit::Context, _error: wginit::WgpuInitError) {
+        // Without a usable adapter, this still covers tearing down the window.
+        ctxt.event_loop.exit();
+    }
+
+    fn finished(self) {
+        FINISHED.store(true, std::sync::atomic::Ordering::Release);
+    }
+}
+
+#[test]
+#[ignore = "requires a display"]
+fn exit_after_init() {
+    wginit::run_on_thread::<App>().unwrap().join().unwrap();
+    assert!(FINISHED.load(std::sync::atomic::Ordering::Acquire));
+}